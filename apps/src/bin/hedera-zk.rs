@@ -0,0 +1,302 @@
+// Command-line front end of the prover.
+//
+// `hedera-zk prove --input <file>` proves the main program on the inputs in <file> and writes
+// `journal.bin`, `receipt.bin` (bincode) and, for Groth16 receipts, `seal.bin` to the output
+// directory. The input file is either JSON describing a BLS-signed inclusion proof:
+//   { "root": "0x…", "leaf": "0x…", "pubkey": "0x…", "signature": "0x…", "path": ["0x…", …] }
+// proven with sorted-pair SHA-384 hashing under the min-pk basic DST, or the hex of private inputs
+// in the guest's wire format (`core/src/inputs.rs`) for any other statement or mode.
+//
+// `hedera-zk verify --receipt <file>` checks a receipt of either guest program against the image id
+// of the program that commits its journal, and prints what the journal states.
+
+use std::{fmt::Display, fs, path::PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use apps::{prove, verify, JournalContents, ReceiptKind};
+use clap::{Parser, Subcommand, ValueEnum};
+use hedera_zk_core::bls::{BlsVariant, Ciphersuite, MIN_PK_NUL_DST};
+use hedera_zk_core::inputs::{Hash, PrivateInputs, RootSignature, Statement};
+use hedera_zk_core::journal::{JournalFlags, JournalMode, JournalPayload};
+use hedera_zk_core::merkle::{HashMode, MerkleMode};
+use risc0_zkvm::Receipt;
+use serde::Deserialize;
+
+#[derive(Parser)]
+#[command(name = "hedera-zk", about = "Prove that a leaf is committed under a signed root")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prove the main program on an input file.
+    Prove {
+        /// JSON inclusion proof, or hex-encoded private inputs.
+        #[arg(long)]
+        input: PathBuf,
+        /// How far to prove the run.
+        #[arg(long, value_enum, default_value_t = Kind::Groth16)]
+        receipt_kind: Kind,
+        /// Directory the journal, seal and receipt are written to.
+        #[arg(long, default_value = ".")]
+        out: PathBuf,
+    },
+    /// Verify a stored receipt against its guest program and print its journal.
+    Verify {
+        /// Receipt written by `prove`.
+        #[arg(long)]
+        receipt: PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Kind {
+    Composite,
+    Succinct,
+    Groth16,
+}
+
+impl From<Kind> for ReceiptKind {
+    fn from(kind: Kind) -> Self {
+        match kind {
+            Kind::Composite => ReceiptKind::Composite,
+            Kind::Succinct => ReceiptKind::Succinct,
+            Kind::Groth16 => ReceiptKind::Groth16,
+        }
+    }
+}
+
+// A BLS-signed inclusion proof, all fields hex with an optional 0x prefix.
+#[derive(Deserialize)]
+struct InclusionFile {
+    root: String,
+    leaf: String,
+    pubkey: String,
+    signature: String,
+    path: Vec<String>,
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim().trim_start_matches("0x")).with_context(|| format!("Invalid hex in {field}"))
+}
+
+fn decode_hash(field: &str, value: &str) -> Result<Hash> {
+    decode_hex(field, value)?
+        .try_into()
+        .map_err(|_| anyhow!("{field} is not a 48-byte hash"))
+}
+
+fn read_inputs(contents: &str) -> Result<PrivateInputs> {
+    if !contents.trim_start().starts_with('{') {
+        return PrivateInputs::decode(&decode_hex("input", contents)?).map_err(|err| anyhow!("Invalid private inputs: {err}"));
+    }
+
+    let file: InclusionFile = serde_json::from_str(contents).context("Invalid JSON input")?;
+    let merkle_path = file
+        .path
+        .iter()
+        .enumerate()
+        .map(|(i, element)| decode_hash(&format!("path[{i}]"), element))
+        .collect::<Result<_>>()?;
+    Ok(PrivateInputs {
+        merkle_root: decode_hash("root", &file.root)?,
+        context: None,
+        root_signature: RootSignature::Bls {
+            pubkey: decode_hex("pubkey", &file.pubkey)?,
+            signature: decode_hex("signature", &file.signature)?,
+        },
+        ciphersuite: Ciphersuite { variant: BlsVariant::MinPk, dst: MIN_PK_NUL_DST.to_vec() },
+        merkle_mode: MerkleMode::Sorted,
+        hash_mode: HashMode::Plain,
+        journal_mode: JournalMode::Full,
+        nullifier: None,
+        statement: Statement::Inclusion { leaf: decode_hash("leaf", &file.leaf)?, leaf_index: 0, merkle_path },
+    })
+}
+
+fn row(label: &str, value: impl Display) {
+    println!("{:<19}{}", format!("{label}:"), value);
+}
+
+fn print_flags(flags: &JournalFlags) {
+    row("hash mode", format!("{:?}", flags.hash_mode));
+    row("merkle mode", format!("{:?}", flags.merkle_mode));
+    row("BLS variant", format!("{:?}", flags.bls_variant));
+    row("signature scheme", format!("{:?}", flags.signature_scheme));
+    row("depth", flags.depth);
+}
+
+fn print_context(bound: bool, domain: &[u8], chain_id: u64, epoch: u64) {
+    if bound {
+        row("signing context", format!("{:?}, chain {chain_id}, epoch {epoch}", String::from_utf8_lossy(domain)));
+    } else {
+        row("signing context", "none, the bare root was signed");
+    }
+}
+
+fn print_nullifier(nullifier: &[u8], domain: &[u8]) {
+    if nullifier.is_empty() {
+        row("nullifier", "none");
+    } else {
+        row("nullifier", format!("0x{} for {:?}", hex::encode(nullifier), String::from_utf8_lossy(domain)));
+    }
+}
+
+fn print_report(receipt: &Receipt, contents: &JournalContents) {
+    let kind = ReceiptKind::of(receipt).map_or("fake (dev mode)".to_string(), |kind| format!("{kind:?}"));
+    row("receipt", format!("{kind}, verified against image id {}", contents.image_id()));
+
+    let journal = match contents {
+        JournalContents::Full(journal) => journal,
+        JournalContents::Compact(journal) => {
+            row("layout", "compact");
+            row("statement", format!("{:?}", journal.statement_type));
+            print_flags(&journal.flags);
+            row("root", format!("0x{}", hex::encode(journal.root)));
+            row("leaf", format!("0x{}", hex::encode(journal.leaf)));
+            row("leaf index", journal.leaf_index);
+            row("signer hash", format!("0x{}", hex::encode(journal.signer_hash)));
+            return;
+        }
+    };
+
+    row("layout", if matches!(journal.payload, JournalPayload::Private(..)) { "private" } else { "full" });
+    row("statement", format!("{:?}", journal.payload.statement_type()));
+    print_flags(&journal.flags);
+    match &journal.payload {
+        JournalPayload::Inclusion(inputs) => {
+            row("merkle root", &inputs.merkle_root);
+            row("leaf", &inputs.leaf);
+            row("leaf index", inputs.leaf_index);
+            row("signer", &inputs.signer);
+            print_context(inputs.context_bound, &inputs.domain, inputs.chain_id, inputs.epoch);
+            print_nullifier(&inputs.nullifier, &inputs.nullifier_domain);
+        }
+        JournalPayload::Multiproof(inputs) => {
+            row("merkle root", &inputs.merkle_root);
+            for (leaf, index) in inputs.leaves.iter().zip(&inputs.leaf_indices) {
+                row("leaf", format!("{leaf} at index {index}"));
+            }
+            row("signer", &inputs.signer);
+            print_context(inputs.context_bound, &inputs.domain, inputs.chain_id, inputs.epoch);
+        }
+        JournalPayload::Sparse(inputs) => {
+            row("merkle root", &inputs.merkle_root);
+            row("key", &inputs.key);
+            if inputs.absent {
+                row("value", "absent");
+            } else {
+                row("value", &inputs.value);
+            }
+            row("signer", &inputs.signer);
+            print_context(inputs.context_bound, &inputs.domain, inputs.chain_id, inputs.epoch);
+        }
+        JournalPayload::RecordStream(inputs) => {
+            row("start running hash", &inputs.start_running_hash);
+            row("end running hash", &inputs.end_running_hash);
+            row("record hash", &inputs.record_hash);
+            row("position", format!("{} of {}", inputs.position, inputs.record_count));
+            row("signer", &inputs.signer);
+            print_context(inputs.context_bound, &inputs.domain, inputs.chain_id, inputs.epoch);
+            print_nullifier(&inputs.nullifier, &inputs.nullifier_domain);
+        }
+        JournalPayload::Rotation(inputs) => {
+            row("old address book", &inputs.old_address_book_hash);
+            row("new address book", &inputs.new_address_book_hash);
+            row("signed stake", format!("{} of {}", inputs.signed_stake, inputs.total_stake));
+        }
+        JournalPayload::Private(_, inputs) => {
+            row("merkle root", &inputs.merkle_root);
+            row("leaf commitment", &inputs.leaf_commitment);
+            row("signer hash", &inputs.signer_hash);
+            print_context(inputs.context_bound, &inputs.domain, inputs.chain_id, inputs.epoch);
+            print_nullifier(&inputs.nullifier, &inputs.nullifier_domain);
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Prove { input, receipt_kind, out } => {
+            let contents = fs::read_to_string(&input).with_context(|| format!("Failed to read {}", input.display()))?;
+            let inputs = read_inputs(&contents)?;
+
+            let bundle = prove(&inputs, receipt_kind.into())?;
+
+            fs::create_dir_all(&out).with_context(|| format!("Failed to create {}", out.display()))?;
+            fs::write(out.join("journal.bin"), &bundle.journal).context("Failed to write the journal")?;
+            fs::write(out.join("receipt.bin"), bincode::serialize(&bundle.receipt)?).context("Failed to write the receipt")?;
+            if !bundle.seal.is_empty() {
+                fs::write(out.join("seal.bin"), &bundle.seal).context("Failed to write the seal")?;
+            }
+
+            println!("image id:          {}", bundle.image_id);
+            println!("post state digest: {}", bundle.post_state_digest);
+            println!("written to:        {}", out.display());
+        }
+        Command::Verify { receipt } => {
+            let bytes = fs::read(&receipt).with_context(|| format!("Failed to read {}", receipt.display()))?;
+            let receipt: Receipt = bincode::deserialize(&bytes).context("Invalid receipt file")?;
+
+            let contents = verify(&receipt)?;
+            print_report(&receipt, &contents);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inclusion_file(root: &str) -> String {
+        format!(
+            r#"{{ "root": "{root}", "leaf": "0x{}", "pubkey": "0x{}", "signature": "{}", "path": ["0x{}", "{}"] }}"#,
+            "02".repeat(48),
+            "af".repeat(48),
+            "b0".repeat(96),
+            "03".repeat(48),
+            "04".repeat(48),
+        )
+    }
+
+    #[test]
+    fn test_read_json_inputs() {
+        let inputs = read_inputs(&inclusion_file(&format!("0x{}", "01".repeat(48)))).unwrap();
+
+        assert_eq!(inputs.merkle_root, [1; 48]);
+        assert_eq!(inputs.root_signature, RootSignature::Bls { pubkey: vec![0xaf; 48], signature: vec![0xb0; 96] });
+        assert_eq!(inputs.ciphersuite, Ciphersuite { variant: BlsVariant::MinPk, dst: MIN_PK_NUL_DST.to_vec() });
+        assert_eq!((inputs.merkle_mode, inputs.hash_mode), (MerkleMode::Sorted, HashMode::Plain));
+        assert_eq!(inputs.journal_mode, JournalMode::Full);
+        assert_eq!((&inputs.context, &inputs.nullifier), (&None, &None));
+        assert_eq!(inputs.statement, Statement::Inclusion { leaf: [2; 48], leaf_index: 0, merkle_path: vec![[3; 48], [4; 48]] });
+    }
+
+    #[test]
+    fn test_read_hex_inputs() {
+        let inputs = read_inputs(&inclusion_file(&"01".repeat(48))).unwrap();
+        let encoded = hex::encode(inputs.encode());
+
+        // Surrounding whitespace and a 0x prefix are accepted, as in the JSON fields.
+        assert_eq!(read_inputs(&encoded).unwrap(), inputs);
+        assert_eq!(read_inputs(&format!("0x{encoded}\n")).unwrap(), inputs);
+    }
+
+    #[test]
+    fn test_read_malformed_inputs() {
+        let error = |contents: &str| read_inputs(contents).unwrap_err().to_string();
+
+        assert!(error(r#"{ "root": "0x01" }"#).starts_with("Invalid JSON input"));
+        assert_eq!(error(&inclusion_file("0x01")), "root is not a 48-byte hash");
+        assert!(error(&inclusion_file(&"zz".repeat(48))).starts_with("Invalid hex in root"));
+        assert!(error("not hex").starts_with("Invalid hex in input"));
+        assert!(error("00").starts_with("Invalid private inputs"));
+
+        // Hex inputs cut short do not decode.
+        let encoded = hex::encode(read_inputs(&inclusion_file(&"01".repeat(48))).unwrap().encode());
+        assert!(error(&encoded[..encoded.len() - 2]).starts_with("Invalid private inputs"));
+    }
+}
@@ -0,0 +1,228 @@
+// Host side of the main program: runs it on private inputs and proves the run, as far as a seal the
+// on-chain verifier accepts, and checks stored receipts before they are used.
+//
+// Proving goes through up to three stages, each feeding the next:
+//   composite   one STARK per segment of the execution
+//   succinct    the segment receipts compressed into a single recursion receipt
+//   Groth16     the succinct receipt wrapped in a SNARK, which needs Docker for `stark_to_snark`
+
+use hedera_zk_core::inputs::PrivateInputs;
+use hedera_zk_core::journal::{decode_journal, CompactJournal, DecodedJournal, JournalPayload};
+use methods::{MAIN_ELF, MAIN_ID, ROTATION_ID};
+use risc0_groth16::docker::stark_to_snark;
+use risc0_zkvm::{
+    get_prover_server,
+    recursion::identity_p254,
+    sha::{Digest, Digestible},
+    CompactReceipt, ExecutorEnv, ExecutorImpl, InnerReceipt, ProverOpts, Receipt, VerificationError,
+    VerifierContext,
+};
+
+/// How far a run is proven.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReceiptKind {
+    Composite,
+    Succinct,
+    Groth16, // the only kind `CommitmentVerification.sol` can verify
+}
+
+impl ReceiptKind {
+    /// The kind of a receipt, None for the fake receipts of dev mode.
+    pub fn of(receipt: &Receipt) -> Option<Self> {
+        match &receipt.inner {
+            InnerReceipt::Composite(_) => Some(ReceiptKind::Composite),
+            InnerReceipt::Succinct(_) => Some(ReceiptKind::Succinct),
+            InnerReceipt::Compact(_) => Some(ReceiptKind::Groth16),
+            _ => None,
+        }
+    }
+}
+
+/// A proven run of the main program, with everything a contract call needs.
+#[derive(Clone, Debug)]
+pub struct ProofBundle {
+    pub journal: Vec<u8>,
+    pub seal: Vec<u8>, // Groth16 seal for the on-chain verifier, empty for the other kinds
+    pub post_state_digest: Digest,
+    pub image_id: Digest,
+    pub receipt: Receipt,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProveError {
+    #[error("failed to build the executor environment")]
+    Environment(#[source] anyhow::Error),
+    #[error("guest execution failed")]
+    Execution(#[source] anyhow::Error),
+    #[error("guest committed no journal")]
+    MissingJournal,
+    #[error("failed to prove the session")]
+    Proving(#[source] anyhow::Error),
+    #[error("failed to compress the receipt")]
+    Compression(#[source] anyhow::Error),
+    #[error("failed to wrap the receipt in a Groth16 proof")]
+    Groth16(#[source] anyhow::Error),
+}
+
+/// A journal of either guest program, in either layout of the main program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JournalContents {
+    Full(Box<DecodedJournal>),
+    Compact(CompactJournal),
+}
+
+impl JournalContents {
+    /// Image id of the guest program that commits journals like this one.
+    pub fn image_id(&self) -> Digest {
+        match self {
+            JournalContents::Full(journal) if matches!(journal.payload, JournalPayload::Rotation(_)) => Digest::from(ROTATION_ID),
+            _ => Digest::from(MAIN_ID),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+    #[error("receipt does not verify against the guest program of its journal")]
+    Receipt(#[source] VerificationError),
+    #[error("invalid journal: {0}")]
+    Journal(&'static str),
+}
+
+/// Runs the main program on `inputs` and proves the run up to a receipt of the given kind.
+pub fn prove(inputs: &PrivateInputs, kind: ReceiptKind) -> Result<ProofBundle, ProveError> {
+    let env = ExecutorEnv::builder()
+        .write_slice(&inputs.encode())
+        .build()
+        .map_err(ProveError::Environment)?;
+    let session = ExecutorImpl::from_elf(env, MAIN_ELF)
+        .and_then(|mut exec| exec.run())
+        .map_err(ProveError::Execution)?;
+    let journal = session.journal.clone().ok_or(ProveError::MissingJournal)?.bytes;
+
+    let prover = get_prover_server(&ProverOpts::default()).map_err(ProveError::Proving)?;
+    let receipt = prover
+        .prove_session(&VerifierContext::default(), &session)
+        .map_err(ProveError::Proving)?;
+    let claim = receipt.get_claim().map_err(ProveError::Proving)?;
+    let post_state_digest = claim.post.digest();
+
+    let image_id = Digest::from(MAIN_ID);
+    if kind == ReceiptKind::Composite {
+        return Ok(ProofBundle { journal, seal: Vec::new(), post_state_digest, image_id, receipt });
+    }
+
+    let composite = receipt.inner.composite().map_err(ProveError::Compression)?;
+    let succinct = prover.compress(composite).map_err(ProveError::Compression)?;
+    if kind == ReceiptKind::Succinct {
+        let receipt = Receipt::new(InnerReceipt::Succinct(succinct), journal.clone());
+        return Ok(ProofBundle { journal, seal: Vec::new(), post_state_digest, image_id, receipt });
+    }
+
+    let ident_receipt = identity_p254(&succinct).map_err(ProveError::Groth16)?;
+    let seal = stark_to_snark(&ident_receipt.get_seal_bytes())
+        .map_err(ProveError::Groth16)?
+        .to_vec();
+    let receipt = Receipt::new(InnerReceipt::Compact(CompactReceipt { seal: seal.clone(), claim }), journal.clone());
+    Ok(ProofBundle { journal, seal, post_state_digest, image_id, receipt })
+}
+
+/// Decodes the journal of a receipt of any kind and verifies the receipt against the image id of
+/// the guest program that commits such journals: the rotation program for address book rotations,
+/// the main program otherwise. The journal only picks the image id; the receipt binds it to it.
+pub fn verify(receipt: &Receipt) -> Result<JournalContents, VerifyError> {
+    // ABI-encoded journals start with the zero high bytes of an offset, compact ones with the version.
+    let journal = &receipt.journal.bytes;
+    let contents = if journal.starts_with(&[0, 0]) {
+        decode_journal(journal).map(|journal| JournalContents::Full(Box::new(journal))).map_err(VerifyError::Journal)?
+    } else {
+        CompactJournal::decode(journal).map(JournalContents::Compact).map_err(VerifyError::Journal)?
+    };
+
+    receipt.verify(contents.image_id()).map_err(VerifyError::Receipt)?;
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::FixedBytes;
+    use sha2::{Digest as _, Sha384};
+    use std::str::FromStr;
+
+    use hedera_zk_core::bls::{BlsVariant, Ciphersuite, MIN_PK_NUL_DST};
+    use hedera_zk_core::inputs::{RootSignature, Statement};
+    use hedera_zk_core::journal::{JournalFlags, JournalMode, PublicInputs, RotationPublicInputs, SignatureScheme};
+    use hedera_zk_core::merkle::{compute_merkle_root, HashMode, MerkleMode};
+
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+
+        // Precomputed example inputs
+        let leaf_data = b"example leaf data";
+        let leaf_hash = Sha384::digest(leaf_data);
+        let leaf: [u8; 48] = leaf_hash.into();
+
+        // 48 byte padded numbers 0 to 255 concatenated
+        let serialized_path = FixedBytes::<12288>::from_str("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000aa0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ab0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ac0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ad0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ae0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000af0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ba0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000bb0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000bc0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000bd0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000be0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000bf0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ca0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000cb0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000cc0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000cd0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ce0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000cf0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000da0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000db0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000dc0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000dd0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000de0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000df0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ea0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000eb0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ec0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ed0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ee0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ef0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000fa0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000fb0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000fc0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000fd0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000fe0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ff00000000000000000000000000000000").unwrap();
+
+        let merkle_path: Vec<[u8; 48]> = serialized_path.chunks_exact(48).map(|element| element.try_into().unwrap()).collect();
+
+        
+        let computed_root: [u8; 48] = compute_merkle_root(MerkleMode::Sorted, HashMode::Plain, &leaf, 0, &merkle_path);
+        println!("initial computed_root: {:?}", computed_root);
+
+        let merkle_root = computed_root;
+        // let bls_privkey: PrivateKey = PrivateKey::generate(&mut rand::thread_rng());
+        // let bls_pubkey: PublicKey = bls_privkey.public_key();
+        // let bls_signature: Signature = bls_privkey.sign(&(merkle_root.as_slice()));
+
+        let bls_pubkey = FixedBytes::<48>::from_str("af991965245f23d0e8c498f95fb0293c3923f9ff68b24b93866570f49d9eb66afc19f156934f80edd52f42c3dcf41784").unwrap();
+        let bls_signature = FixedBytes::<96>::from_str("af08d283cbf25c4863294e49d08080b6bb9861e92d7ccc16434c4d355bf3c22f7858e7cb63fc3461d3250ce250822f9a1274952d7282c1f585a48b2572497c727beaf295ba817c5c112d5a4603c0fe864caeadab040c9ad5db47441ac8aef259").unwrap();
+        
+        let statement = Statement::Inclusion { leaf, leaf_index: 0, merkle_path };
+        let root_signature = RootSignature::Bls { pubkey: bls_pubkey.to_vec(), signature: bls_signature.to_vec() };
+        let ciphersuite = Ciphersuite { variant: BlsVariant::MinPk, dst: MIN_PK_NUL_DST.to_vec() };
+        let private_inputs = PrivateInputs {
+            merkle_root,
+            context: None,
+            root_signature,
+            ciphersuite,
+            merkle_mode: MerkleMode::Sorted,
+            hash_mode: HashMode::Plain,
+            journal_mode: JournalMode::Full,
+            nullifier: None,
+            statement,
+        };
+
+        let bundle = prove(&private_inputs, ReceiptKind::Groth16).unwrap();
+        assert_eq!(bundle.image_id, Digest::from(MAIN_ID));
+        assert_eq!(bundle.receipt.journal.bytes, bundle.journal);
+        assert!(!bundle.seal.is_empty());
+
+        assert_eq!(ReceiptKind::of(&bundle.receipt), Some(ReceiptKind::Groth16));
+
+        let contents = verify(&bundle.receipt).expect("Receipt does not verify");
+        assert_eq!(contents.image_id(), Digest::from(MAIN_ID));
+        let JournalContents::Full(decoded) = contents else { panic!("Not a full journal") };
+        let JournalPayload::Inclusion(public_inputs) = decoded.payload else { panic!("Not an inclusion journal") };
+        assert_eq!(public_inputs.leaf.to_vec(), leaf.to_vec());
+    }
+
+    #[test]
+    fn test_rotation_image_id() {
+        let flags = JournalFlags {
+            hash_mode: HashMode::Plain,
+            merkle_mode: MerkleMode::Sorted,
+            bls_variant: BlsVariant::MinPk,
+            signature_scheme: SignatureScheme::BlsQuorum,
+            depth: 0,
+        };
+        let rotation = DecodedJournal { flags, payload: JournalPayload::Rotation(RotationPublicInputs::default()) };
+        assert_eq!(JournalContents::Full(Box::new(rotation)).image_id(), Digest::from(ROTATION_ID));
+
+        let inclusion = DecodedJournal { flags, payload: JournalPayload::Inclusion(PublicInputs::default()) };
+        assert_eq!(JournalContents::Full(Box::new(inclusion)).image_id(), Digest::from(MAIN_ID));
+    }
+}
@@ -19,6 +19,162 @@ pragma solidity ^0.8.20;
 import {IRiscZeroVerifier} from "risc0/IRiscZeroVerifier.sol";
 import {ImageID} from "./ImageID.sol"; // auto-generated contract after running `cargo build`.
 
+/// @notice Header every guest journal starts with, wrapping the ABI-encoded payload struct.
+/// @dev `flags` packs, least significant bit first, the hash mode (bit 0), merkle mode (bit 1),
+///      BLS variant (bit 2), compact layout (bit 3, only ever set in a `CompactJournal`), private
+///      layout (bit 4), reserved zero bits (5-7), signature scheme (bits 8-15) and path depth
+///      (bits 16-31). See `core/src/journal.rs`.
+struct Journal {
+    uint16 version;
+    uint8 statementType;
+    uint32 flags;
+    bytes payload;
+}
+
+/// @notice Fixed-layout journal the main guest program commits in compact mode.
+/// @dev 143 bytes, big-endian, see `core/src/journal.rs`. `flags` has bit 3 set and a signature
+///      scheme of 0: only roots signed by a single BLS key have a compact journal. `signerHash` is
+///      sha256(abi.encodePacked(uint32(signer.length), signer, blsDstHash, contextBound, uint32(domain.length),
+///      domain, chainId, epoch)), so it binds the DST and signing context along with the signer.
+struct CompactJournal {
+    uint16 version;
+    uint8 statementType;
+    uint32 flags;
+    bytes merkleRoot;
+    bytes leaf;
+    uint64 leafIndex;
+    bytes32 signerHash;
+}
+
+/// @notice Payload committed by the main guest program for inclusion proofs.
+struct PublicInputs {
+    bytes merkleRoot;
+    bytes leaf;
+    bytes signer;
+    bytes signature;
+    uint32 depth;
+    uint8 merkleMode;
+    uint64 leafIndex;
+    uint8 hashMode;
+    uint8 signatureScheme;
+    uint8 blsVariant;
+    bytes blsDstHash;
+    bool contextBound;
+    bytes domain;
+    uint64 chainId;
+    uint64 epoch;
+    bytes signerParams;
+    bytes nullifier;
+    bytes nullifierDomain;
+}
+
+/// @notice Payload committed by the main guest program for single-leaf statements in private mode.
+struct PrivatePublicInputs {
+    bytes merkleRoot;
+    bytes leafCommitment;
+    bytes signerHash;
+    uint8 signatureScheme;
+    uint8 blsVariant;
+    bytes blsDstHash;
+    bool contextBound;
+    bytes domain;
+    uint64 chainId;
+    uint64 epoch;
+    bytes signerParams;
+    bytes nullifier;
+    bytes nullifierDomain;
+}
+
+/// @notice Payload committed by the main guest program for multiproofs.
+struct MultiproofPublicInputs {
+    bytes merkleRoot;
+    bytes[] leaves;
+    bytes signer;
+    bytes signature;
+    uint32 depth;
+    uint8 merkleMode;
+    uint64[] leafIndices;
+    uint8 hashMode;
+    uint8 signatureScheme;
+    uint8 blsVariant;
+    bytes blsDstHash;
+    bool contextBound;
+    bytes domain;
+    uint64 chainId;
+    uint64 epoch;
+    bytes signerParams;
+}
+
+/// @notice Payload committed by the main guest program for sparse tree proofs.
+struct SparsePublicInputs {
+    bytes merkleRoot;
+    bytes key;
+    bool absent;
+    bytes value;
+    bytes signer;
+    bytes signature;
+    uint32 depth;
+    uint8 hashMode;
+    uint8 signatureScheme;
+    uint8 blsVariant;
+    bytes blsDstHash;
+    bool contextBound;
+    bytes domain;
+    uint64 chainId;
+    uint64 epoch;
+    bytes signerParams;
+}
+
+/// @notice Payload committed by the main guest program for record stream proofs.
+struct RecordStreamPublicInputs {
+    bytes startRunningHash;
+    bytes endRunningHash;
+    bytes recordHash;
+    bytes signer;
+    bytes signature;
+    uint32 position;
+    uint32 recordCount;
+    uint8 signatureScheme;
+    uint8 blsVariant;
+    bytes blsDstHash;
+    bool contextBound;
+    bytes domain;
+    uint64 chainId;
+    uint64 epoch;
+    bytes signerParams;
+    bytes nullifier;
+    bytes nullifierDomain;
+}
+
+/// @notice Signer parameters of roots signed by a stake quorum of an address book.
+struct QuorumParams {
+    uint64 thresholdNumerator;
+    uint64 thresholdDenominator;
+    uint64 signedStake;
+    uint64 totalStake;
+}
+
+/// @notice What every payload of the main program records about how its root was signed. The
+///         signer of private payloads is their signer hash, which for quorums is the address book hash.
+struct RootSigner {
+    bytes signer;
+    uint8 blsVariant;
+    bytes blsDstHash;
+    bytes signerParams;
+}
+
+/// @notice Payload committed by the `rotation` guest program.
+struct RotationPublicInputs {
+    bytes oldAddressBookHash;
+    bytes newAddressBookHash;
+    uint64 thresholdNumerator;
+    uint64 thresholdDenominator;
+    uint64 signedStake;
+    uint64 totalStake;
+    uint8 blsVariant;
+    bytes blsDstHash;
+}
+
 /// @title A starter application using RISC Zero.
 /// @notice This basic application holds a number, guaranteed to be even.
 /// @dev This contract demonstrates one pattern for offloading the computation of an expensive
@@ -32,14 +188,135 @@ contract CommitmentVerification {
     ///         ensuring that only proofs generated from a pre-defined guest program
     ///         (in this case, checking if a number is even) are considered valid.
     bytes32 public constant imageId = ImageID.MAIN_ID;
+    /// @notice Image ID of the guest program proving address book rotations.
+    bytes32 public constant rotationImageId = ImageID.ROTATION_ID;
+
+    /// @notice Smallest stake fraction of the trusted address book that may sign a root or a
+    ///         rotation (1/3). The guest requires strictly more than the threshold to sign.
+    uint64 public constant MIN_QUORUM_NUMERATOR = 1;
+    uint64 public constant MIN_QUORUM_DENOMINATOR = 3;
 
+    /// @notice Journal layout version this contract understands.
+    uint16 public constant JOURNAL_VERSION = 2;
+    /// @notice Statement types of the main program that do not start like `SingleLeafSigner`.
+    uint8 public constant MULTIPROOF_STATEMENT = 1;
+    uint8 public constant SPARSE_STATEMENT = 2;
+    uint8 public constant RECORD_STREAM_STATEMENT = 3;
+    /// @notice Statement type of address book rotation journals.
+    uint8 public constant ROTATION_STATEMENT = 4;
+    /// @notice Journal flag set when the payload is `PrivatePublicInputs`.
+    uint32 public constant PRIVATE_FLAG = 1 << 4;
+    /// @notice Signature scheme of roots signed by a single BLS key, the only one compact journals hold.
+    uint8 public constant BLS_SCHEME = 0;
+    /// @notice Signature scheme of roots signed by a stake quorum, whose signer is the address book hash.
+    uint8 public constant BLS_QUORUM_SCHEME = 2;
 
-    /// @notice Initialize the contract, binding it to a specified RISC Zero verifier.
-    constructor(IRiscZeroVerifier _verifier) {
+    /// @notice SHA-384 hash of the address book currently trusted to sign roots.
+    bytes public addressBookHash;
+    /// @notice SHA-384 hash of the BLS domain separation tag the trusted address book signs under.
+    bytes public blsDstHash;
+    /// @notice BLS key layout of the trusted address book, 0 for min-pk and 1 for min-sig.
+    uint8 public blsVariant;
+
+    /// @notice Initialize the contract, binding it to a specified RISC Zero verifier and the
+    ///         initially trusted address book with the ciphersuite its nodes sign under.
+    constructor(IRiscZeroVerifier _verifier, bytes memory _addressBookHash, bytes memory _blsDstHash, uint8 _blsVariant) {
         verifier = _verifier;
+        addressBookHash = _addressBookHash;
+        blsDstHash = _blsDstHash;
+        blsVariant = _blsVariant;
     }
 
+    /// @notice Verify a proof of the main program. Roots signed by an address book quorum are only
+    ///         accepted from the address book currently trusted, under its ciphersuite and with a
+    ///         threshold of at least the minimum quorum.
     function verify(bytes memory journal, bytes32 postStateDigest, bytes calldata seal) public returns (bool){
-        require(verifier.verify(seal, imageId, postStateDigest, sha256(journal)), "verification failed");   
+        require(verifier.verify(seal, imageId, postStateDigest, sha256(journal)), "verification failed");
+
+        // ABI-encoded journals start with the zero high bytes of an offset, compact ones with the version.
+        if (journal[0] != 0 || journal[1] != 0) {
+            require(journal.length == 143, "invalid compact journal");
+            require((uint16(uint8(journal[0])) << 8 | uint8(journal[1])) == JOURNAL_VERSION, "unsupported journal version");
+            require(uint8(journal[5]) == BLS_SCHEME, "compact journals only hold single-key signatures");
+            return true;
+        }
+
+        Journal memory header = abi.decode(journal, (Journal));
+        require(header.version == JOURNAL_VERSION, "unsupported journal version");
+        if (uint8(header.flags >> 8) == BLS_QUORUM_SCHEME) {
+            RootSigner memory rootSigner = signerOf(header);
+            require(keccak256(rootSigner.signer) == keccak256(addressBookHash), "root not signed by the current address book");
+            requireTrustedCiphersuite(rootSigner.blsVariant, rootSigner.blsDstHash);
+            QuorumParams memory quorum = abi.decode(rootSigner.signerParams, (QuorumParams));
+            requireMinimumQuorum(quorum.thresholdNumerator, quorum.thresholdDenominator);
+        }
+        return true;
+    }
+
+    /// @notice How the root of a journal of the main program was signed.
+    function signerOf(Journal memory header) internal pure returns (RootSigner memory) {
+        if (header.flags & PRIVATE_FLAG != 0) {
+            PrivatePublicInputs memory inputs = abi.decode(header.payload, (PrivatePublicInputs));
+            return RootSigner(inputs.signerHash, inputs.blsVariant, inputs.blsDstHash, inputs.signerParams);
+        }
+        if (header.statementType == MULTIPROOF_STATEMENT) {
+            MultiproofPublicInputs memory inputs = abi.decode(header.payload, (MultiproofPublicInputs));
+            return RootSigner(inputs.signer, inputs.blsVariant, inputs.blsDstHash, inputs.signerParams);
+        }
+        if (header.statementType == SPARSE_STATEMENT) {
+            SparsePublicInputs memory inputs = abi.decode(header.payload, (SparsePublicInputs));
+            return RootSigner(inputs.signer, inputs.blsVariant, inputs.blsDstHash, inputs.signerParams);
+        }
+        if (header.statementType == RECORD_STREAM_STATEMENT) {
+            RecordStreamPublicInputs memory inputs = abi.decode(header.payload, (RecordStreamPublicInputs));
+            return RootSigner(inputs.signer, inputs.blsVariant, inputs.blsDstHash, inputs.signerParams);
+        }
+        PublicInputs memory inclusion = abi.decode(header.payload, (PublicInputs));
+        return RootSigner(inclusion.signer, inclusion.blsVariant, inclusion.blsDstHash, inclusion.signerParams);
+    }
+
+    /// @notice Revert unless signatures were checked under the trusted address book's ciphersuite.
+    function requireTrustedCiphersuite(uint8 variant, bytes memory dstHash) internal view {
+        require(variant == blsVariant && keccak256(dstHash) == keccak256(blsDstHash), "not signed under the trusted ciphersuite");
+    }
+
+    /// @notice Revert unless a quorum threshold is at least the minimum quorum.
+    function requireMinimumQuorum(uint64 numerator, uint64 denominator) internal pure {
+        require(
+            uint128(numerator) * MIN_QUORUM_DENOMINATOR >= uint128(MIN_QUORUM_NUMERATOR) * denominator,
+            "quorum threshold too small"
+        );
+    }
+
+    /// @notice Split a compact journal into its fields.
+    function decodeCompactJournal(bytes calldata journal) public pure returns (CompactJournal memory) {
+        require(journal.length == 143, "invalid compact journal");
+        require(uint16(bytes2(journal[0:2])) == JOURNAL_VERSION, "unsupported journal version");
+        return CompactJournal({
+            version: uint16(bytes2(journal[0:2])),
+            statementType: uint8(journal[2]),
+            flags: uint32(bytes4(journal[3:7])),
+            merkleRoot: journal[7:55],
+            leaf: journal[55:103],
+            leafIndex: uint64(bytes8(journal[103:111])),
+            signerHash: bytes32(journal[111:143])
+        });
+    }
+
+    /// @notice Move trust to the next address book, given a rotation proof signed by a quorum of the
+    ///         current one.
+    function rotateAddressBook(bytes memory journal, bytes32 postStateDigest, bytes calldata seal) public {
+        require(verifier.verify(seal, rotationImageId, postStateDigest, sha256(journal)), "verification failed");
+
+        Journal memory header = abi.decode(journal, (Journal));
+        require(header.version == JOURNAL_VERSION, "unsupported journal version");
+        require(header.statementType == ROTATION_STATEMENT, "not a rotation journal");
+        RotationPublicInputs memory rotation = abi.decode(header.payload, (RotationPublicInputs));
+        require(keccak256(rotation.oldAddressBookHash) == keccak256(addressBookHash), "rotation does not start from the current address book");
+        // Both books are hashed under the same key layout, so the ciphersuite carries over.
+        requireTrustedCiphersuite(rotation.blsVariant, rotation.blsDstHash);
+        requireMinimumQuorum(rotation.thresholdNumerator, rotation.thresholdDenominator);
+
+        addressBookHash = rotation.newAddressBookHash;
     }
 }
@@ -0,0 +1,100 @@
+// Stake-weighted address books.
+//
+// An address book lists every node by id with its BLS public key and stake. It is identified by the
+// SHA-384 hash of the BLS variant of its keys (1 byte) followed by its entries in node id order,
+// each encoded as
+// `node_id (8 bytes, big-endian) || pubkey length (4 bytes, big-endian) || pubkey || stake (8 bytes, big-endian)`.
+// Keys must have the compressed length of the variant, so entries cannot be merged into or split
+// out of one another without changing the hash.
+
+use sha2::{Digest, Sha384};
+
+use crate::bls::BlsVariant;
+
+/// Smallest quorum threshold accepted: Hedera finality needs strictly more than a third of the stake.
+pub const MIN_THRESHOLD_NUMERATOR: u64 = 1;
+pub const MIN_THRESHOLD_DENOMINATOR: u64 = 3;
+
+/// Hashes address book entries given as `(node_id, pubkey, stake)` in node id order. Fails if a key
+/// does not have the compressed length of `variant`.
+pub fn hash_address_book<'a>(
+    variant: BlsVariant,
+    entries: impl IntoIterator<Item = (u64, &'a [u8], u64)>,
+) -> Result<[u8; 48], &'static str> {
+    let mut hasher = Sha384::new();
+    hasher.update([variant as u8]);
+    for (node_id, pubkey, stake) in entries {
+        if pubkey.len() != variant.public_key_len() {
+            return Err("Address book key has the wrong length");
+        }
+        hasher.update(node_id.to_be_bytes());
+        hasher.update((pubkey.len() as u32).to_be_bytes());
+        hasher.update(pubkey);
+        hasher.update(stake.to_be_bytes());
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// Returns true if `numerator / denominator` is at least the minimum threshold and below one.
+pub fn threshold_is_valid(numerator: u64, denominator: u64) -> bool {
+    numerator < denominator
+        && u128::from(numerator) * u128::from(MIN_THRESHOLD_DENOMINATOR)
+            >= u128::from(denominator) * u128::from(MIN_THRESHOLD_NUMERATOR)
+}
+
+/// Returns true if `signed_stake` is strictly more than `numerator / denominator` of `total_stake`.
+pub fn quorum_reached(signed_stake: u64, total_stake: u64, numerator: u64, denominator: u64) -> bool {
+    u128::from(signed_stake) * u128::from(denominator) > u128::from(total_stake) * u128::from(numerator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quorum_threshold() {
+        // Hedera finality needs strictly more than a third of the stake.
+        assert!(!quorum_reached(100, 300, 1, 3));
+        assert!(quorum_reached(101, 300, 1, 3));
+        assert!(quorum_reached(u64::MAX, u64::MAX, 2, 3));
+        assert!(!quorum_reached(0, 0, 1, 3));
+
+        assert!(threshold_is_valid(1, 3));
+        assert!(threshold_is_valid(2, 3));
+        assert!(threshold_is_valid(u64::MAX - 1, u64::MAX));
+        // A zero threshold would accept any signed stake, and a threshold of one none.
+        assert!(!threshold_is_valid(0, 3));
+        assert!(!threshold_is_valid(0, 0));
+        assert!(!threshold_is_valid(3, 3));
+        assert!(!threshold_is_valid(1, 4));
+    }
+
+    #[test]
+    fn test_folded_address_book() {
+        let keys = [[1u8; 48], [2u8; 48]];
+        let book = [(3u64, &keys[0][..], 10u64), (4, &keys[1][..], 20)];
+
+        // Without length prefixes, the first entry's key could absorb the rest of the book, leaving
+        // an entry with a malformed key whose stake only pads the total.
+        let mut folded_key = keys[0].to_vec();
+        folded_key.extend_from_slice(&10u64.to_be_bytes());
+        folded_key.extend_from_slice(&4u64.to_be_bytes());
+        folded_key.extend_from_slice(&keys[1]);
+        let folded = [(3u64, folded_key.as_slice(), 20u64)];
+        let unprefixed = |entries: &[(u64, &[u8], u64)]| -> [u8; 48] {
+            let mut hasher = Sha384::new();
+            for (node_id, pubkey, stake) in entries {
+                hasher.update(node_id.to_be_bytes());
+                hasher.update(pubkey);
+                hasher.update(stake.to_be_bytes());
+            }
+            hasher.finalize().into()
+        };
+        assert_eq!(unprefixed(&book), unprefixed(&folded));
+
+        assert!(hash_address_book(BlsVariant::MinPk, book).is_ok());
+        assert!(hash_address_book(BlsVariant::MinPk, folded).is_err());
+        // Keys are checked against the variant the book is hashed under.
+        assert!(hash_address_book(BlsVariant::MinSig, book).is_err());
+    }
+}
@@ -0,0 +1,572 @@
+// BLS signature verification over BLS12-381. The host tests check it against `bls-signatures`.
+//
+// Both layouts of the IETF ciphersuites are supported. Min-pk, the one `bls-signatures` uses, has
+// 48-byte compressed G1 public keys and 96-byte compressed G2 signatures. Min-sig, used by Hedera
+// TSS among others, swaps the groups: 96-byte G2 public keys and 48-byte G1 signatures. Messages are
+// hashed to the signature group with SHA-256 `expand_message_xmd` under a caller-chosen domain
+// separation tag.
+
+use alloc::vec::Vec;
+
+use bls12_381::{
+    hash_to_curve::{ExpandMsgXmd, HashToCurve},
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt, Scalar,
+};
+use sha2::{Digest, Sha384};
+
+use crate::merkle::merkle_tree_hash;
+
+/// DST of the basic min-pk scheme, used by `bls-signatures`.
+pub const MIN_PK_NUL_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+/// DST of the min-pk proof-of-possession scheme, used by Ethereum consensus.
+pub const MIN_PK_POP_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// DST of the basic min-sig scheme.
+pub const MIN_SIG_NUL_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+/// DST of the min-sig proof-of-possession scheme.
+pub const MIN_SIG_POP_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+// Tags of the proof-of-possession scheme's PopProve, which signs the compressed public key.
+const MIN_PK_POP_PROVE_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const MIN_SIG_POP_PROVE_DST: &[u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+// RFC 9380 requires a non-empty tag of at most 255 bytes.
+pub const MAX_DST_LEN: usize = 255;
+
+/// Which group holds the public keys and which the signatures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlsVariant {
+    /// G1 public keys and G2 signatures.
+    MinPk = 0,
+    /// G2 public keys and G1 signatures.
+    MinSig = 1,
+}
+
+impl BlsVariant {
+    /// Length of a compressed public key.
+    pub fn public_key_len(self) -> usize {
+        match self {
+            BlsVariant::MinPk => 48,
+            BlsVariant::MinSig => 96,
+        }
+    }
+}
+
+/// Key layout and hash-to-curve domain separation tag that signatures are checked under.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ciphersuite {
+    pub variant: BlsVariant,
+    pub dst: Vec<u8>,
+}
+
+impl Ciphersuite {
+    /// Returns true if the tag is one `expand_message_xmd` accepts as is.
+    pub fn dst_is_valid(&self) -> bool {
+        !self.dst.is_empty() && self.dst.len() <= MAX_DST_LEN
+    }
+
+    /// SHA-384 hash of the domain separation tag, as committed in the journal.
+    pub fn dst_hash(&self) -> [u8; 48] {
+        Sha384::digest(&self.dst).into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PublicKey {
+    G1(G1Affine),
+    G2(G2Affine),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Signature {
+    G1(G1Affine),
+    G2(G2Affine),
+}
+
+impl PublicKey {
+    /// Compressed encoding, as accepted by `public_key`.
+    pub fn to_bytes(self) -> Vec<u8> {
+        match self {
+            PublicKey::G1(point) => point.to_compressed().to_vec(),
+            PublicKey::G2(point) => point.to_compressed().to_vec(),
+        }
+    }
+}
+
+impl Signature {
+    /// Compressed encoding, as accepted by `signature`.
+    pub fn to_bytes(self) -> Vec<u8> {
+        match self {
+            Signature::G1(point) => point.to_compressed().to_vec(),
+            Signature::G2(point) => point.to_compressed().to_vec(),
+        }
+    }
+}
+
+fn decode_g1(bytes: &[u8]) -> Option<G1Affine> {
+    G1Affine::from_compressed(bytes.try_into().ok()?).into()
+}
+
+fn decode_g2(bytes: &[u8]) -> Option<G2Affine> {
+    G2Affine::from_compressed(bytes.try_into().ok()?).into()
+}
+
+/// Decodes a compressed public key, rejecting points of the wrong size, outside the subgroup or at
+/// the identity.
+pub fn public_key(variant: BlsVariant, bytes: &[u8]) -> Option<PublicKey> {
+    match variant {
+        BlsVariant::MinPk => decode_g1(bytes).filter(|key| !bool::from(key.is_identity())).map(PublicKey::G1),
+        BlsVariant::MinSig => decode_g2(bytes).filter(|key| !bool::from(key.is_identity())).map(PublicKey::G2),
+    }
+}
+
+/// Decodes a compressed signature, rejecting points of the wrong size or outside the subgroup.
+pub fn signature(variant: BlsVariant, bytes: &[u8]) -> Option<Signature> {
+    match variant {
+        BlsVariant::MinPk => decode_g2(bytes).map(Signature::G2),
+        BlsVariant::MinSig => decode_g1(bytes).map(Signature::G1),
+    }
+}
+
+/// Sums public keys into the key that verifies the aggregate of their signatures over one message.
+/// Returns `None` if a key belongs to the other variant.
+pub fn aggregate_public_keys<'a>(
+    variant: BlsVariant,
+    keys: impl IntoIterator<Item = &'a PublicKey>,
+) -> Option<PublicKey> {
+    let (mut g1, mut g2) = (G1Projective::identity(), G2Projective::identity());
+    for key in keys {
+        match (variant, key) {
+            (BlsVariant::MinPk, PublicKey::G1(key)) => g1 += key,
+            (BlsVariant::MinSig, PublicKey::G2(key)) => g2 += key,
+            _ => return None,
+        }
+    }
+
+    Some(match variant {
+        BlsVariant::MinPk => PublicKey::G1(g1.into()),
+        BlsVariant::MinSig => PublicKey::G2(g2.into()),
+    })
+}
+
+// Lagrange coefficients for interpolating at zero from the shares held at `indices`. Returns `None`
+// if an index repeats.
+fn lagrange_coefficients(indices: &[u64]) -> Option<Vec<Scalar>> {
+    let xs: Vec<Scalar> = indices.iter().map(|&index| Scalar::from(index)).collect();
+    xs.iter()
+        .enumerate()
+        .map(|(i, xi)| {
+            let (numerator, denominator) = xs
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold((Scalar::one(), Scalar::one()), |(numerator, denominator), (_, xj)| {
+                    (numerator * xj, denominator * (xj - xi))
+                });
+            Option::<Scalar>::from(denominator.invert()).map(|inverse| numerator * inverse)
+        })
+        .collect()
+}
+
+/// Combines signature shares from the signers at `indices` into the group signature by Lagrange
+/// interpolation at zero. Signer indices are the non-zero points the secret polynomial was evaluated
+/// at. Returns `None` if an index is zero or repeats, or if the shares are not all in one group.
+pub fn combine_signature_shares(indices: &[u64], shares: &[Signature]) -> Option<Signature> {
+    if shares.is_empty() || indices.len() != shares.len() || indices.contains(&0) {
+        return None;
+    }
+    let coefficients = lagrange_coefficients(indices)?;
+
+    match shares[0] {
+        Signature::G1(_) => {
+            let mut sum = G1Projective::identity();
+            for (share, coefficient) in shares.iter().zip(&coefficients) {
+                let Signature::G1(share) = share else { return None };
+                sum += share * coefficient;
+            }
+            Some(Signature::G1(sum.into()))
+        }
+        Signature::G2(_) => {
+            let mut sum = G2Projective::identity();
+            for (share, coefficient) in shares.iter().zip(&coefficients) {
+                let Signature::G2(share) = share else { return None };
+                sum += share * coefficient;
+            }
+            Some(Signature::G2(sum.into()))
+        }
+    }
+}
+
+/// Identifies a threshold key set by its group public key, the threshold its shares were dealt with
+/// and the public key shares of signers 1 to n:
+/// `SHA-384(group_pubkey || threshold (4 bytes, big-endian) || merkle_tree_hash(key_shares))`.
+/// A combined signature verifies the same whichever shares went into it, so the threshold only
+/// means something once every share is checked against a key share of a key set verifiers
+/// registered under this id.
+pub fn key_set_id<S: AsRef<[u8]>>(group_pubkey: &[u8], threshold: u32, key_shares: &[S]) -> [u8; 48] {
+    Sha384::new()
+        .chain_update(group_pubkey)
+        .chain_update(threshold.to_be_bytes())
+        .chain_update(merkle_tree_hash(key_shares))
+        .finalize()
+        .into()
+}
+
+/// A message hashed into the signature group, with the parts of the pairing that do not depend on
+/// the signer prepared once. Hashing to G2 and precomputing its Miller loop lines are a large part
+/// of a verification, so signatures over a shared message should all be checked against a single
+/// `PreparedMessage`.
+pub struct PreparedMessage {
+    hashed: HashedMessage,
+    // SHA-384 of the tag and message, which batch weights are derived from.
+    transcript: [u8; 48],
+}
+
+enum HashedMessage {
+    // Min-pk: `H(message)` in G2, ready for the Miller loop.
+    G2(G2Prepared),
+    // Min-sig: `H(message)` in G1, with the G2 generator ready for the Miller loop.
+    G1(G1Affine, G2Prepared),
+}
+
+/// Hashes `message` to the signature group of `variant` under `dst`.
+pub fn prepare_message(variant: BlsVariant, message: &[u8], dst: &[u8]) -> PreparedMessage {
+    let hashed = match variant {
+        BlsVariant::MinPk => {
+            let hashed: G2Affine =
+                <G2Projective as HashToCurve<ExpandMsgXmd<sha2_09::Sha256>>>::hash_to_curve(message, dst).into();
+            HashedMessage::G2(G2Prepared::from(hashed))
+        }
+        BlsVariant::MinSig => {
+            let hashed: G1Affine =
+                <G1Projective as HashToCurve<ExpandMsgXmd<sha2_09::Sha256>>>::hash_to_curve(message, dst).into();
+            HashedMessage::G1(hashed, G2Prepared::from(G2Affine::generator()))
+        }
+    };
+    let transcript = Sha384::new()
+        .chain_update((dst.len() as u64).to_be_bytes())
+        .chain_update(dst)
+        .chain_update(message)
+        .finalize()
+        .into();
+
+    PreparedMessage { hashed, transcript }
+}
+
+/// Checks `e(pubkey, H(message)) == e(g, signature)`, with the pairing arguments swapped for
+/// min-sig, using a single final exponentiation.
+pub fn verify_prepared(pubkey: &PublicKey, message: &PreparedMessage, signature: &Signature) -> bool {
+    let result = match (pubkey, &message.hashed, signature) {
+        (PublicKey::G1(pubkey), HashedMessage::G2(hashed), Signature::G2(signature)) => {
+            if bool::from(pubkey.is_identity()) {
+                return false;
+            }
+            multi_miller_loop(&[(pubkey, hashed), (&-G1Affine::generator(), &G2Prepared::from(*signature))])
+        }
+        (PublicKey::G2(pubkey), HashedMessage::G1(hashed, generator), Signature::G1(signature)) => {
+            if bool::from(pubkey.is_identity()) {
+                return false;
+            }
+            multi_miller_loop(&[(hashed, &G2Prepared::from(*pubkey)), (&-signature, generator)])
+        }
+        _ => return false,
+    };
+
+    result.final_exponentiation() == Gt::identity()
+}
+
+/// Checks many signatures over one message with a single pairing check, by testing a random linear
+/// combination: `e(sum r_i * pubkey_i, H(message)) == e(g, sum r_i * signature_i)`. The 128-bit
+/// weights are a hash of the message and every key and signature, so they are fixed only after the
+/// whole batch is, and an invalid signature passes with negligible probability. A failed batch does
+/// not say which signature is invalid; callers fall back to `verify_prepared` for that.
+pub fn verify_batch(message: &PreparedMessage, batch: &[(PublicKey, Signature)]) -> bool {
+    let mut transcript = Sha384::new().chain_update(message.transcript);
+    for (pubkey, signature) in batch {
+        transcript.update(pubkey.to_bytes());
+        transcript.update(signature.to_bytes());
+    }
+    let seed = transcript.finalize();
+    let weights: Vec<u128> = (0..batch.len() as u64)
+        .map(|index| {
+            let digest = Sha384::new().chain_update(seed).chain_update(index.to_be_bytes()).finalize();
+            u128::from_le_bytes(digest[..16].try_into().unwrap())
+        })
+        .collect();
+
+    // The weighted sums share one doubling per weight bit across the whole batch (Shamir's trick),
+    // which is far cheaper than a full-width scalar multiplication per point.
+    let result = match &message.hashed {
+        HashedMessage::G2(hashed) => {
+            let mut points = Vec::with_capacity(batch.len());
+            for pair in batch {
+                let (PublicKey::G1(pubkey), Signature::G2(signature)) = pair else { return false };
+                if bool::from(pubkey.is_identity()) {
+                    return false;
+                }
+                points.push((pubkey, signature));
+            }
+
+            let (mut pubkeys, mut signatures) = (G1Projective::identity(), G2Projective::identity());
+            for bit in (0..128).rev() {
+                pubkeys = pubkeys.double();
+                signatures = signatures.double();
+                for (&(pubkey, signature), weight) in points.iter().zip(&weights) {
+                    if weight >> bit & 1 == 1 {
+                        pubkeys += pubkey;
+                        signatures += signature;
+                    }
+                }
+            }
+            multi_miller_loop(&[
+                (&G1Affine::from(pubkeys), hashed),
+                (&-G1Affine::generator(), &G2Prepared::from(G2Affine::from(signatures))),
+            ])
+        }
+        HashedMessage::G1(hashed, generator) => {
+            let mut points = Vec::with_capacity(batch.len());
+            for pair in batch {
+                let (PublicKey::G2(pubkey), Signature::G1(signature)) = pair else { return false };
+                if bool::from(pubkey.is_identity()) {
+                    return false;
+                }
+                points.push((pubkey, signature));
+            }
+
+            let (mut pubkeys, mut signatures) = (G2Projective::identity(), G1Projective::identity());
+            for bit in (0..128).rev() {
+                pubkeys = pubkeys.double();
+                signatures = signatures.double();
+                for (&(pubkey, signature), weight) in points.iter().zip(&weights) {
+                    if weight >> bit & 1 == 1 {
+                        pubkeys += pubkey;
+                        signatures += signature;
+                    }
+                }
+            }
+            multi_miller_loop(&[
+                (hashed, &G2Prepared::from(G2Affine::from(pubkeys))),
+                (&-G1Affine::from(signatures), generator),
+            ])
+        }
+    };
+
+    result.final_exponentiation() == Gt::identity()
+}
+
+/// Verifies one signature over `message`, hashed to the curve under `dst`.
+pub fn verify(pubkey: &PublicKey, message: &[u8], signature: &Signature, dst: &[u8]) -> bool {
+    let variant = match pubkey {
+        PublicKey::G1(_) => BlsVariant::MinPk,
+        PublicKey::G2(_) => BlsVariant::MinSig,
+    };
+    verify_prepared(pubkey, &prepare_message(variant, message, dst), signature)
+}
+
+/// Checks a proof of possession: a signature over the compressed public key under the PopProve tag.
+/// Keys must pass this before they are aggregated, or one signer can cancel out the others.
+pub fn verify_possession(pubkey: &PublicKey, proof: &Signature) -> bool {
+    match pubkey {
+        PublicKey::G1(key) => verify(pubkey, &key.to_compressed(), proof, MIN_PK_POP_PROVE_DST),
+        PublicKey::G2(key) => verify(pubkey, &key.to_compressed(), proof, MIN_SIG_POP_PROVE_DST),
+    }
+}
+
+/// Lists the committee members marked in a participation bitmap, where member `i` is bit `i % 8`
+/// of byte `i / 8`. The bitmap must be exactly long enough for the committee, with unused bits clear.
+pub fn participants(committee_size: usize, bitmap: &[u8]) -> Option<Vec<usize>> {
+    if bitmap.len() != committee_size.div_ceil(8) {
+        return None;
+    }
+    let marked: Vec<usize> = (0..bitmap.len() * 8)
+        .filter(|i| bitmap[i / 8] >> (i % 8) & 1 == 1)
+        .collect();
+    match marked.last() {
+        Some(&last) if last >= committee_size => None,
+        _ => Some(marked),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use sha2::{Digest, Sha384};
+
+    use crate::bls;
+
+    use super::*;
+
+    #[test]
+    fn test_aggregate_bls() {
+        use bls_signatures::{aggregate, PrivateKey, Serialize};
+
+        let root = Sha384::digest(b"root");
+        let committee: Vec<PrivateKey> = (1..=10u8).map(|i| PrivateKey::new([i; 32])).collect();
+        let pubkeys: Vec<Vec<u8>> = committee.iter().map(|key| key.public_key().as_bytes()).collect();
+
+        // Members 0, 2 and 9 sign.
+        let participation = [0b0000_0101, 0b0000_0010];
+        let members = bls::participants(committee.len(), &participation).unwrap();
+        assert_eq!(members, [0, 2, 9]);
+        let signatures: Vec<_> = members.iter().map(|&member| committee[member].sign(root)).collect();
+        let signature = bls::signature(BlsVariant::MinPk, &aggregate(&signatures).unwrap().as_bytes()).unwrap();
+
+        let keys: Vec<_> = members.iter().map(|&member| bls::public_key(BlsVariant::MinPk, &pubkeys[member]).unwrap()).collect();
+        let aggregate_key = |keys: &[bls::PublicKey]| bls::aggregate_public_keys(BlsVariant::MinPk, keys).unwrap();
+        assert!(bls::verify(&aggregate_key(&keys), &root, &signature, MIN_PK_NUL_DST));
+        assert!(!bls::verify(&aggregate_key(&keys[..2]), &root, &signature, MIN_PK_NUL_DST));
+        assert!(!bls::verify(&aggregate_key(&keys), b"other root", &signature, MIN_PK_NUL_DST));
+
+        // bls-signatures signs under the basic scheme tag only.
+        assert!(!bls::verify(&aggregate_key(&keys), &root, &signature, MIN_PK_POP_DST));
+
+        // Each member's own signature checks against the message hashed once.
+        let prepared = bls::prepare_message(BlsVariant::MinPk, &root, MIN_PK_NUL_DST);
+        for (&member, key) in members.iter().zip(&keys) {
+            let signature = bls::signature(BlsVariant::MinPk, &committee[member].sign(root).as_bytes()).unwrap();
+            assert!(bls::verify_prepared(key, &prepared, &signature));
+        }
+
+        // The bitmap must cover exactly the committee.
+        assert!(bls::participants(committee.len(), &participation[..1]).is_none());
+        assert!(bls::participants(committee.len(), &[0b0000_0101, 0b0000_0100]).is_none());
+    }
+
+    #[test]
+    fn test_batch_verification() {
+        use bls_signatures::{PrivateKey, Serialize};
+
+        let root = Sha384::digest(b"root");
+        let prepared = bls::prepare_message(BlsVariant::MinPk, &root, MIN_PK_NUL_DST);
+        let mut batch: Vec<_> = (1..=8u8)
+            .map(|node| {
+                let key = PrivateKey::new([node; 32]);
+                (
+                    bls::public_key(BlsVariant::MinPk, &key.public_key().as_bytes()).unwrap(),
+                    bls::signature(BlsVariant::MinPk, &key.sign(root).as_bytes()).unwrap(),
+                )
+            })
+            .collect();
+        assert!(bls::verify_batch(&prepared, &batch));
+
+        // One bad signature fails the whole batch, even though its own check is what finds it.
+        batch[3].1 = batch[4].1;
+        assert!(!bls::verify_batch(&prepared, &batch));
+        assert!(!bls::verify_prepared(&batch[3].0, &prepared, &batch[3].1));
+        assert!(bls::verify_prepared(&batch[4].0, &prepared, &batch[4].1));
+
+        // Swapped signatures cancel out in an unweighted sum, but not under the batch weights.
+        batch[3].1 = batch[5].1;
+        batch[5].1 = bls::signature(BlsVariant::MinPk, &PrivateKey::new([4; 32]).sign(root).as_bytes()).unwrap();
+        assert!(!bls::verify_batch(&prepared, &batch));
+    }
+
+    #[test]
+    fn test_min_sig_variant() {
+        use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+        use bls12_381::{G1Affine, G1Projective, G2Affine, Scalar};
+
+        // bls-signatures only signs min-pk, so sign with the curve directly, under a custom tag.
+        let dst = b"HEDERA_TSS_BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        let secret = Scalar::from(0x5eed_u64);
+        let message = Sha384::digest(b"root");
+        let hashed = <G1Projective as HashToCurve<ExpandMsgXmd<sha2_09::Sha256>>>::hash_to_curve(message, dst);
+        let pubkey = G2Affine::from(G2Affine::generator() * secret).to_compressed();
+        let signature = G1Affine::from(hashed * secret).to_compressed();
+
+        let key = bls::public_key(BlsVariant::MinSig, &pubkey).unwrap();
+        let sig = bls::signature(BlsVariant::MinSig, &signature).unwrap();
+        assert!(bls::verify(&key, &message, &sig, dst));
+        assert!(!bls::verify(&key, b"other root", &sig, dst));
+        assert!(!bls::verify(&key, &message, &sig, MIN_SIG_NUL_DST));
+        assert!(bls::verify_prepared(&key, &bls::prepare_message(BlsVariant::MinSig, &message, dst), &sig));
+        assert!(!bls::verify_prepared(&key, &bls::prepare_message(BlsVariant::MinPk, &message, dst), &sig));
+
+        let ciphersuite = Ciphersuite { variant: BlsVariant::MinSig, dst: dst.to_vec() };
+        assert!(ciphersuite.dst_is_valid());
+        assert_eq!(ciphersuite.dst_hash(), <[u8; 48]>::from(Sha384::digest(dst)));
+        assert!(!Ciphersuite { variant: BlsVariant::MinSig, dst: Vec::new() }.dst_is_valid());
+        assert!(!Ciphersuite { variant: BlsVariant::MinSig, dst: vec![b'x'; 256] }.dst_is_valid());
+
+        // The layouts cannot be confused for one another.
+        assert!(bls::public_key(BlsVariant::MinPk, &pubkey).is_none());
+        assert!(bls::signature(BlsVariant::MinPk, &signature).is_none());
+        assert!(bls::aggregate_public_keys(BlsVariant::MinPk, [&key]).is_none());
+    }
+
+    #[test]
+    fn test_proof_of_possession() {
+        use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+        use bls12_381::{G1Affine, G2Affine, G2Projective, Scalar};
+
+        let pop_prove = |secret: Scalar, pubkey: &[u8]| {
+            let hashed = <G2Projective as HashToCurve<ExpandMsgXmd<sha2_09::Sha256>>>::hash_to_curve(
+                pubkey,
+                b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_",
+            );
+            G2Affine::from(hashed * secret).to_compressed()
+        };
+        let (honest, attacker) = (Scalar::from(7u64), Scalar::from(11u64));
+        let honest_pubkey = G1Affine::from(G1Affine::generator() * honest).to_compressed();
+        let attacker_pubkey = G1Affine::from(G1Affine::generator() * attacker).to_compressed();
+
+        let key = bls::public_key(BlsVariant::MinPk, &honest_pubkey).unwrap();
+        let proof = bls::signature(BlsVariant::MinPk, &pop_prove(honest, &honest_pubkey)).unwrap();
+        assert!(bls::verify_possession(&key, &proof));
+
+        // A proof made for another key, or made by someone else, does not carry over.
+        let other_proof = bls::signature(BlsVariant::MinPk, &pop_prove(honest, &attacker_pubkey)).unwrap();
+        assert!(!bls::verify_possession(&key, &other_proof));
+        let forged_proof = bls::signature(BlsVariant::MinPk, &pop_prove(attacker, &honest_pubkey)).unwrap();
+        assert!(!bls::verify_possession(&key, &forged_proof));
+    }
+
+    #[test]
+    fn test_threshold_signature() {
+        use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+        use bls12_381::{G1Affine, G2Affine, G2Projective, Scalar};
+
+        // 2-of-3 sharing of the group secret 42 with f(x) = 42 + 5x.
+        let share_secret = |index: u64| Scalar::from(42u64) + Scalar::from(5u64) * Scalar::from(index);
+        let group_pubkey = G1Affine::from(G1Affine::generator() * Scalar::from(42u64)).to_compressed();
+        let key_shares: Vec<_> = (1..=3).map(|index| G1Affine::from(G1Affine::generator() * share_secret(index)).to_compressed()).collect();
+        let key_share = |index: u64| bls::public_key(BlsVariant::MinPk, &key_shares[index as usize - 1]).unwrap();
+        let message = Sha384::digest(b"root");
+        let hashed = <G2Projective as HashToCurve<ExpandMsgXmd<sha2_09::Sha256>>>::hash_to_curve(message, MIN_PK_NUL_DST);
+        let share = |index: u64| {
+            let signature = G2Affine::from(hashed * share_secret(index)).to_compressed();
+            bls::signature(BlsVariant::MinPk, &signature).unwrap()
+        };
+
+        let group_key = bls::public_key(BlsVariant::MinPk, &group_pubkey).unwrap();
+        for indices in [[1, 2], [1, 3], [2, 3]] {
+            let shares: Vec<_> = indices.iter().map(|&index| share(index)).collect();
+            let combined = bls::combine_signature_shares(&indices, &shares).unwrap();
+            assert!(bls::verify(&group_key, &message, &combined, MIN_PK_NUL_DST));
+        }
+
+        // Too few shares, or shares attributed to the wrong signers, do not combine to the group key.
+        let combined = bls::combine_signature_shares(&[1], &[share(1)]).unwrap();
+        assert!(!bls::verify(&group_key, &message, &combined, MIN_PK_NUL_DST));
+        let combined = bls::combine_signature_shares(&[1, 2], &[share(1), share(3)]).unwrap();
+        assert!(!bls::verify(&group_key, &message, &combined, MIN_PK_NUL_DST));
+
+        assert!(bls::combine_signature_shares(&[0, 1], &[share(1), share(2)]).is_none());
+        assert!(bls::combine_signature_shares(&[2, 2], &[share(2), share(2)]).is_none());
+
+        // The group signature passed off as a 1-of-1 share still combines to a valid signature, but
+        // it is not a valid share under the signer's key share.
+        let group_signature = bls::combine_signature_shares(&[1, 2], &[share(1), share(2)]).unwrap();
+        let single = bls::combine_signature_shares(&[1], &[group_signature]).unwrap();
+        assert!(bls::verify(&group_key, &message, &single, MIN_PK_NUL_DST));
+        for index in 1..=3 {
+            assert!(bls::verify(&key_share(index), &message, &share(index), MIN_PK_NUL_DST));
+            assert!(!bls::verify(&key_share(index), &message, &group_signature, MIN_PK_NUL_DST));
+        }
+
+        // The key set id binds the threshold and every key share.
+        let id = bls::key_set_id(&group_pubkey, 2, &key_shares);
+        assert_ne!(bls::key_set_id(&group_pubkey, 1, &key_shares), id);
+        assert_ne!(bls::key_set_id(&group_pubkey, 2, &key_shares[..2]), id);
+        assert_ne!(bls::key_set_id(&group_pubkey, 2, &[key_shares[1], key_shares[0], key_shares[2]]), id);
+    }
+}
@@ -0,0 +1,780 @@
+// Private inputs of the guest programs and their canonical wire format.
+//
+// The guests read this format from stdin, so anything that can concatenate bytes can produce it,
+// including `bytes.concat` / `abi.encodePacked` in the Forge tests. Integers are big-endian:
+//   u8, u32, u64   fixed width
+//   hash           48 raw bytes
+//   salt           32 raw bytes
+//   list<T>        u32 count || items; `bytes` is list<u8>
+//   option<T>      u8 0 (absent) or 1 (present) || T
+//   enum           u8 tag || fields of the variant, tags numbered from 0 in declaration order
+// Structs are their fields in declaration order. Decoding rejects truncated input, unknown tags and
+// trailing bytes, so every input has exactly one encoding.
+
+use alloc::vec::Vec;
+
+use crate::bls::{BlsVariant, Ciphersuite};
+use crate::journal::JournalMode;
+use crate::merkle::{HashMode, MerkleMode};
+use crate::message::SigningContext;
+use crate::sig_file::RecordFileHeader;
+
+pub type Hash = [u8; 48];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressBookEntry {
+    pub node_id: u64,
+    pub pubkey: Vec<u8>, // BLS public key in the layout of the ciphersuite's variant
+    pub stake: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeSignature {
+    pub node_id: u64,
+    pub signature: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureShare {
+    pub index: u64, // non-zero point the signer's key share was evaluated at
+    pub signature: Vec<u8>,
+}
+
+// How the root is signed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RootSignature {
+    // A BLS signature over the root.
+    Bls {
+        pubkey: Vec<u8>, // 48 bytes for min-pk, 96 bytes for min-sig
+        signature: Vec<u8>, // 96 bytes for min-pk, 48 bytes for min-sig
+    },
+    // A Hedera node signature file over the record file a record stream statement proves a record
+    // of. The signed metadata hash is recomputed from the statement's start running hash, the root
+    // as end running hash and `header`, so only record stream statements can use it.
+    SignatureFile {
+        node_pubkey: Vec<u8>, // DER SubjectPublicKeyInfo, as published in the address book
+        signature_file: Vec<u8>, // raw .rcd_sig contents, version 5 or 6
+        header: RecordFileHeader,
+    },
+    // BLS signatures over the root from address book nodes holding more than
+    // threshold_numerator / threshold_denominator of the total stake. The threshold must be at
+    // least address_book::MIN_THRESHOLD_NUMERATOR / MIN_THRESHOLD_DENOMINATOR and below one.
+    Quorum {
+        address_book: Vec<AddressBookEntry>, // strictly increasing node ids
+        signatures: Vec<NodeSignature>, // strictly increasing node ids
+        threshold_numerator: u64,
+        threshold_denominator: u64,
+    },
+    // One aggregated BLS signature over the root from the committee members marked in
+    // `participation`. The committee is identified by the RFC 6962 Merkle tree hash of its keys.
+    Aggregate {
+        committee: Vec<Vec<u8>>, // BLS public keys, in committee order
+        participation: Vec<u8>, // bit i % 8 of byte i / 8 set if member i signed
+        signature: Vec<u8>,
+        key_validation: KeyValidation,
+    },
+    // A t-of-n threshold signature over the root, combined in the guest from `threshold` signature
+    // shares by Lagrange interpolation and checked against the group public key. Every share is
+    // first checked against the public key share of its signer, since the group signature alone
+    // would otherwise pass as a single share. The journal commits
+    // bls::key_set_id(group_pubkey, threshold, key_shares) for verifiers to match against the key
+    // sets they registered.
+    Threshold {
+        group_pubkey: Vec<u8>,
+        threshold: u32,
+        key_shares: Vec<Vec<u8>>, // public key shares of signers 1 to n, in index order
+        shares: Vec<SignatureShare>, // exactly `threshold` shares, strictly increasing indices
+    },
+}
+
+// How aggregated keys are shown not to be rogue keys, which could otherwise cancel out honest
+// signers. Committed with the aggregate so verifiers know what they are trusting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyValidation {
+    // A proof of possession for every participating member, checked before aggregation.
+    ProofsOfPossession {
+        proofs: Vec<Option<Vec<u8>>>, // one slot per committee member, None if it did not sign
+    },
+    // The committee root is a registry of keys whose proofs of possession were checked when they
+    // were registered. Verifiers must only accept committee roots from such a registry.
+    Registry,
+}
+
+// What is being proven about the signed root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Statement {
+    // A single leaf and its authentication path.
+    Inclusion {
+        leaf: Hash,
+        leaf_index: u64, // position of the leaf, must be 0 in Sorted mode
+        merkle_path: Vec<Hash>, // at most MAX_MERKLE_DEPTH siblings
+    },
+    // Several leaves of one tree sharing a single set of proof nodes. Indexed mode only.
+    Multiproof {
+        depth: u32,
+        leaves: Vec<Hash>,
+        leaf_indices: Vec<u64>, // strictly increasing
+        proof: Vec<Hash>, // bottom-up, left to right within a level
+    },
+    // The value held by a key in a sparse Merkle tree, or its absence when `value` is None.
+    Sparse {
+        key: Hash,
+        value: Option<Hash>,
+        siblings: Vec<Option<Hash>>, // leaf level first, None for an empty subtree
+    },
+    // A record hash inside a Hedera record file. The signed root is the file's end running hash.
+    RecordStream {
+        start_running_hash: Hash,
+        record_hashes: Vec<Hash>, // every object hash in the file, in order
+        position: u32, // index of the proven record in record_hashes
+    },
+}
+
+// What a nullifier for the proven leaf is derived from, see `privacy::nullifier`. The leaf must be
+// privacy::claimable_leaf(secret, data), so the secret cannot be chosen freely.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NullifierInputs {
+    pub secret: [u8; 32], // known only to whoever may claim the leaf
+    pub data: Vec<u8>, // what the leaf holds besides the secret
+    pub domain: Vec<u8>, // application the claim is made to, e.g. b"example-airdrop/v1"
+}
+
+/// Inputs of the main guest program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrivateInputs {
+    pub merkle_root: Hash,
+    pub context: Option<SigningContext>, // None if the bare root was signed
+    pub root_signature: RootSignature,
+    pub ciphersuite: Ciphersuite, // BLS key layout and DST that root_signature is checked under
+    pub merkle_mode: MerkleMode,
+    pub hash_mode: HashMode,
+    pub journal_mode: JournalMode,
+    pub nullifier: Option<NullifierInputs>, // single-leaf statements only, not with compact journals
+    pub statement: Statement,
+}
+
+/// Inputs of the address book rotation guest program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RotationInputs {
+    pub old_address_book: Vec<AddressBookEntry>,
+    pub new_address_book: Vec<AddressBookEntry>,
+    pub signatures: Vec<NodeSignature>, // old book nodes signing the new book hash
+    pub threshold_numerator: u64,
+    pub threshold_denominator: u64,
+    pub ciphersuite: Ciphersuite, // BLS key layout of both books and DST of the signatures
+}
+
+impl PrivateInputs {
+    /// Encodes the inputs in the canonical wire format.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write(&mut out);
+        out
+    }
+
+    /// Decodes inputs in the canonical wire format, rejecting anything but a single exact encoding.
+    pub fn decode(bytes: &[u8]) -> Result<Self, &'static str> {
+        decode_all(bytes)
+    }
+}
+
+impl RotationInputs {
+    /// Encodes the inputs in the canonical wire format.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write(&mut out);
+        out
+    }
+
+    /// Decodes inputs in the canonical wire format, rejecting anything but a single exact encoding.
+    pub fn decode(bytes: &[u8]) -> Result<Self, &'static str> {
+        decode_all(bytes)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], &'static str> {
+        if len > self.bytes.len() {
+            return Err("Input truncated");
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+}
+
+fn decode_all<T: Wire>(bytes: &[u8]) -> Result<T, &'static str> {
+    let mut reader = Reader { bytes };
+    let value = T::read(&mut reader)?;
+    if !reader.bytes.is_empty() {
+        return Err("Trailing bytes in input");
+    }
+    Ok(value)
+}
+
+// A value with a canonical encoding. Every encoding is at least one byte long.
+trait Wire: Sized {
+    fn write(&self, out: &mut Vec<u8>);
+    fn read(reader: &mut Reader) -> Result<Self, &'static str>;
+}
+
+impl Wire for u8 {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.push(*self);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(reader.take(1)?[0])
+    }
+}
+
+impl Wire for u32 {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_be_bytes());
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(u32::from_be_bytes(reader.take(4)?.try_into().unwrap()))
+    }
+}
+
+impl Wire for u64 {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_be_bytes());
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(u64::from_be_bytes(reader.take(8)?.try_into().unwrap()))
+    }
+}
+
+impl Wire for Hash {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(reader.take(48)?.try_into().unwrap())
+    }
+}
+
+impl Wire for [u8; 32] {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(reader.take(32)?.try_into().unwrap())
+    }
+}
+
+impl<T: Wire> Wire for Vec<T> {
+    fn write(&self, out: &mut Vec<u8>) {
+        u32::try_from(self.len()).expect("List too long").write(out);
+        for item in self {
+            item.write(out);
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        let count = u32::read(reader)? as usize;
+        // Every item takes at least a byte, which bounds the allocation by the input size.
+        if count > reader.bytes.len() {
+            return Err("Input truncated");
+        }
+        (0..count).map(|_| T::read(reader)).collect()
+    }
+}
+
+impl<T: Wire> Wire for Option<T> {
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            None => out.push(0),
+            Some(value) => {
+                out.push(1);
+                value.write(out);
+            }
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        match u8::read(reader)? {
+            0 => Ok(None),
+            1 => Ok(Some(T::read(reader)?)),
+            _ => Err("Invalid option tag"),
+        }
+    }
+}
+
+impl Wire for MerkleMode {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        match u8::read(reader)? {
+            0 => Ok(MerkleMode::Sorted),
+            1 => Ok(MerkleMode::Indexed),
+            _ => Err("Invalid merkle mode"),
+        }
+    }
+}
+
+impl Wire for HashMode {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        match u8::read(reader)? {
+            0 => Ok(HashMode::Plain),
+            1 => Ok(HashMode::Rfc6962),
+            _ => Err("Invalid hash mode"),
+        }
+    }
+}
+
+impl Wire for JournalMode {
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            JournalMode::Full => out.push(0),
+            JournalMode::Compact => out.push(1),
+            JournalMode::Private { salt } => {
+                out.push(2);
+                salt.write(out);
+            }
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        match u8::read(reader)? {
+            0 => Ok(JournalMode::Full),
+            1 => Ok(JournalMode::Compact),
+            2 => Ok(JournalMode::Private { salt: Wire::read(reader)? }),
+            _ => Err("Invalid journal mode"),
+        }
+    }
+}
+
+impl Wire for BlsVariant {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        match u8::read(reader)? {
+            0 => Ok(BlsVariant::MinPk),
+            1 => Ok(BlsVariant::MinSig),
+            _ => Err("Invalid BLS variant"),
+        }
+    }
+}
+
+impl Wire for Ciphersuite {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.variant.write(out);
+        self.dst.write(out);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(Ciphersuite { variant: Wire::read(reader)?, dst: Wire::read(reader)? })
+    }
+}
+
+impl Wire for RecordFileHeader {
+    fn write(&self, out: &mut Vec<u8>) {
+        for part in self.hapi_version {
+            part.write(out);
+        }
+        self.block_number.write(out);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(RecordFileHeader {
+            hapi_version: [Wire::read(reader)?, Wire::read(reader)?, Wire::read(reader)?],
+            block_number: Wire::read(reader)?,
+        })
+    }
+}
+
+impl Wire for SigningContext {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.domain.write(out);
+        self.chain_id.write(out);
+        self.epoch.write(out);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(SigningContext { domain: Wire::read(reader)?, chain_id: Wire::read(reader)?, epoch: Wire::read(reader)? })
+    }
+}
+
+impl Wire for NullifierInputs {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.secret.write(out);
+        self.data.write(out);
+        self.domain.write(out);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(NullifierInputs { secret: Wire::read(reader)?, data: Wire::read(reader)?, domain: Wire::read(reader)? })
+    }
+}
+
+impl Wire for AddressBookEntry {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.node_id.write(out);
+        self.pubkey.write(out);
+        self.stake.write(out);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(AddressBookEntry { node_id: Wire::read(reader)?, pubkey: Wire::read(reader)?, stake: Wire::read(reader)? })
+    }
+}
+
+impl Wire for NodeSignature {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.node_id.write(out);
+        self.signature.write(out);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(NodeSignature { node_id: Wire::read(reader)?, signature: Wire::read(reader)? })
+    }
+}
+
+impl Wire for SignatureShare {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.index.write(out);
+        self.signature.write(out);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(SignatureShare { index: Wire::read(reader)?, signature: Wire::read(reader)? })
+    }
+}
+
+impl Wire for KeyValidation {
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            KeyValidation::ProofsOfPossession { proofs } => {
+                out.push(0);
+                proofs.write(out);
+            }
+            KeyValidation::Registry => out.push(1),
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        match u8::read(reader)? {
+            0 => Ok(KeyValidation::ProofsOfPossession { proofs: Wire::read(reader)? }),
+            1 => Ok(KeyValidation::Registry),
+            _ => Err("Invalid key validation"),
+        }
+    }
+}
+
+impl Wire for RootSignature {
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            RootSignature::Bls { pubkey, signature } => {
+                out.push(0);
+                pubkey.write(out);
+                signature.write(out);
+            }
+            RootSignature::SignatureFile { node_pubkey, signature_file, header } => {
+                out.push(1);
+                node_pubkey.write(out);
+                signature_file.write(out);
+                header.write(out);
+            }
+            RootSignature::Quorum { address_book, signatures, threshold_numerator, threshold_denominator } => {
+                out.push(2);
+                address_book.write(out);
+                signatures.write(out);
+                threshold_numerator.write(out);
+                threshold_denominator.write(out);
+            }
+            RootSignature::Aggregate { committee, participation, signature, key_validation } => {
+                out.push(3);
+                committee.write(out);
+                participation.write(out);
+                signature.write(out);
+                key_validation.write(out);
+            }
+            RootSignature::Threshold { group_pubkey, threshold, key_shares, shares } => {
+                out.push(4);
+                group_pubkey.write(out);
+                threshold.write(out);
+                key_shares.write(out);
+                shares.write(out);
+            }
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        match u8::read(reader)? {
+            0 => Ok(RootSignature::Bls { pubkey: Wire::read(reader)?, signature: Wire::read(reader)? }),
+            1 => Ok(RootSignature::SignatureFile {
+                node_pubkey: Wire::read(reader)?,
+                signature_file: Wire::read(reader)?,
+                header: Wire::read(reader)?,
+            }),
+            2 => Ok(RootSignature::Quorum {
+                address_book: Wire::read(reader)?,
+                signatures: Wire::read(reader)?,
+                threshold_numerator: Wire::read(reader)?,
+                threshold_denominator: Wire::read(reader)?,
+            }),
+            3 => Ok(RootSignature::Aggregate {
+                committee: Wire::read(reader)?,
+                participation: Wire::read(reader)?,
+                signature: Wire::read(reader)?,
+                key_validation: Wire::read(reader)?,
+            }),
+            4 => Ok(RootSignature::Threshold {
+                group_pubkey: Wire::read(reader)?,
+                threshold: Wire::read(reader)?,
+                key_shares: Wire::read(reader)?,
+                shares: Wire::read(reader)?,
+            }),
+            _ => Err("Invalid root signature"),
+        }
+    }
+}
+
+impl Wire for Statement {
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Statement::Inclusion { leaf, leaf_index, merkle_path } => {
+                out.push(0);
+                leaf.write(out);
+                leaf_index.write(out);
+                merkle_path.write(out);
+            }
+            Statement::Multiproof { depth, leaves, leaf_indices, proof } => {
+                out.push(1);
+                depth.write(out);
+                leaves.write(out);
+                leaf_indices.write(out);
+                proof.write(out);
+            }
+            Statement::Sparse { key, value, siblings } => {
+                out.push(2);
+                key.write(out);
+                value.write(out);
+                siblings.write(out);
+            }
+            Statement::RecordStream { start_running_hash, record_hashes, position } => {
+                out.push(3);
+                start_running_hash.write(out);
+                record_hashes.write(out);
+                position.write(out);
+            }
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        match u8::read(reader)? {
+            0 => Ok(Statement::Inclusion {
+                leaf: Wire::read(reader)?,
+                leaf_index: Wire::read(reader)?,
+                merkle_path: Wire::read(reader)?,
+            }),
+            1 => Ok(Statement::Multiproof {
+                depth: Wire::read(reader)?,
+                leaves: Wire::read(reader)?,
+                leaf_indices: Wire::read(reader)?,
+                proof: Wire::read(reader)?,
+            }),
+            2 => Ok(Statement::Sparse {
+                key: Wire::read(reader)?,
+                value: Wire::read(reader)?,
+                siblings: Wire::read(reader)?,
+            }),
+            3 => Ok(Statement::RecordStream {
+                start_running_hash: Wire::read(reader)?,
+                record_hashes: Wire::read(reader)?,
+                position: Wire::read(reader)?,
+            }),
+            _ => Err("Invalid statement"),
+        }
+    }
+}
+
+impl Wire for PrivateInputs {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.merkle_root.write(out);
+        self.context.write(out);
+        self.root_signature.write(out);
+        self.ciphersuite.write(out);
+        self.merkle_mode.write(out);
+        self.hash_mode.write(out);
+        self.journal_mode.write(out);
+        self.nullifier.write(out);
+        self.statement.write(out);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(PrivateInputs {
+            merkle_root: Wire::read(reader)?,
+            context: Wire::read(reader)?,
+            root_signature: Wire::read(reader)?,
+            ciphersuite: Wire::read(reader)?,
+            merkle_mode: Wire::read(reader)?,
+            hash_mode: Wire::read(reader)?,
+            journal_mode: Wire::read(reader)?,
+            nullifier: Wire::read(reader)?,
+            statement: Wire::read(reader)?,
+        })
+    }
+}
+
+impl Wire for RotationInputs {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.old_address_book.write(out);
+        self.new_address_book.write(out);
+        self.signatures.write(out);
+        self.threshold_numerator.write(out);
+        self.threshold_denominator.write(out);
+        self.ciphersuite.write(out);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        Ok(RotationInputs {
+            old_address_book: Wire::read(reader)?,
+            new_address_book: Wire::read(reader)?,
+            signatures: Wire::read(reader)?,
+            threshold_numerator: Wire::read(reader)?,
+            threshold_denominator: Wire::read(reader)?,
+            ciphersuite: Wire::read(reader)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::{Digest, Sha384};
+
+    use crate::bls::{MIN_PK_NUL_DST, MIN_SIG_NUL_DST};
+
+    use super::*;
+
+    #[test]
+    fn test_input_encoding() {
+        let hash = |i: u8| -> [u8; 48] { Sha384::digest([i]).into() };
+        let ciphersuite = Ciphersuite { variant: BlsVariant::MinPk, dst: MIN_PK_NUL_DST.to_vec() };
+
+        // The layout the Forge test builds with bytes.concat.
+        let inclusion = PrivateInputs {
+            merkle_root: hash(0),
+            context: None,
+            root_signature: RootSignature::Bls { pubkey: vec![1; 48], signature: vec![2; 96] },
+            ciphersuite: ciphersuite.clone(),
+            merkle_mode: MerkleMode::Sorted,
+            hash_mode: HashMode::Plain,
+            journal_mode: JournalMode::Full,
+            nullifier: None,
+            statement: Statement::Inclusion { leaf: hash(1), leaf_index: 0, merkle_path: vec![hash(2), hash(3)] },
+        };
+        let expected = [
+            &hash(0)[..],
+            &[0],
+            &[0], &48u32.to_be_bytes(), &[1; 48], &96u32.to_be_bytes(), &[2; 96],
+            &[0], &43u32.to_be_bytes(), MIN_PK_NUL_DST,
+            &[0], &[0], &[0], &[0],
+            &[0], &hash(1), &0u64.to_be_bytes(), &2u32.to_be_bytes(), &hash(2), &hash(3),
+        ]
+        .concat();
+        assert_eq!(inclusion.encode(), expected);
+
+        let signatures = [
+            RootSignature::SignatureFile {
+                node_pubkey: vec![3; 10],
+                signature_file: vec![4; 20],
+                header: RecordFileHeader { hapi_version: [0, 47, 0], block_number: 1000 },
+            },
+            RootSignature::Quorum {
+                address_book: vec![AddressBookEntry { node_id: 3, pubkey: vec![5; 48], stake: 10 }],
+                signatures: vec![NodeSignature { node_id: 3, signature: vec![6; 96] }],
+                threshold_numerator: 2,
+                threshold_denominator: 3,
+            },
+            RootSignature::Aggregate {
+                committee: vec![vec![7; 48], vec![8; 48]],
+                participation: vec![0b10],
+                signature: vec![9; 96],
+                key_validation: KeyValidation::ProofsOfPossession { proofs: vec![None, Some(vec![10; 96])] },
+            },
+            RootSignature::Aggregate { committee: vec![], participation: vec![], signature: vec![], key_validation: KeyValidation::Registry },
+            RootSignature::Threshold {
+                group_pubkey: vec![11; 48],
+                threshold: 1,
+                key_shares: vec![vec![13; 48], vec![14; 48]],
+                shares: vec![SignatureShare { index: 1, signature: vec![12; 96] }],
+            },
+        ];
+        let statements = [
+            Statement::Multiproof { depth: 2, leaves: vec![hash(4)], leaf_indices: vec![1], proof: vec![hash(5), hash(6)] },
+            Statement::Sparse { key: hash(7), value: None, siblings: vec![None, Some(hash(8))] },
+            Statement::Sparse { key: hash(7), value: Some(hash(9)), siblings: vec![] },
+            Statement::RecordStream { start_running_hash: hash(10), record_hashes: vec![hash(11)], position: 0 },
+        ];
+        let context = SigningContext { domain: b"hedera-zk/state-root".to_vec(), chain_id: 295, epoch: 7 };
+        let mut cases = vec![
+            inclusion.clone(),
+            PrivateInputs {
+                journal_mode: JournalMode::Private { salt: [3; 32] },
+                nullifier: Some(NullifierInputs { secret: [4; 32], data: b"100 tokens".to_vec(), domain: b"example-airdrop/v1".to_vec() }),
+                ..inclusion.clone()
+            },
+        ];
+        for (root_signature, statement) in signatures.into_iter().zip(statements.into_iter().cycle()) {
+            cases.push(PrivateInputs {
+                context: Some(context.clone()),
+                root_signature,
+                ciphersuite: Ciphersuite { variant: BlsVariant::MinSig, dst: MIN_SIG_NUL_DST.to_vec() },
+                merkle_mode: MerkleMode::Indexed,
+                hash_mode: HashMode::Rfc6962,
+                journal_mode: JournalMode::Compact,
+                statement,
+                ..inclusion.clone()
+            });
+        }
+        for inputs in &cases {
+            let encoded = inputs.encode();
+            assert_eq!(PrivateInputs::decode(&encoded).as_ref(), Ok(inputs));
+            // Every encoding is exact: no prefix decodes and trailing bytes are rejected.
+            for len in 0..encoded.len() {
+                assert!(PrivateInputs::decode(&encoded[..len]).is_err());
+            }
+            assert_eq!(PrivateInputs::decode(&[encoded.as_slice(), &[0]].concat()), Err("Trailing bytes in input"));
+        }
+
+        // Unknown tags, and lengths that cannot fit in the input.
+        let mut bad_option = expected.clone();
+        bad_option[48] = 2;
+        assert_eq!(PrivateInputs::decode(&bad_option), Err("Invalid option tag"));
+        let mut bad_signature = expected.clone();
+        bad_signature[49] = 5;
+        assert_eq!(PrivateInputs::decode(&bad_signature), Err("Invalid root signature"));
+        let mut huge_list = expected.clone();
+        huge_list[50..54].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert_eq!(PrivateInputs::decode(&huge_list), Err("Input truncated"));
+
+        let rotation = RotationInputs {
+            old_address_book: vec![AddressBookEntry { node_id: 0, pubkey: vec![1; 48], stake: 5 }],
+            new_address_book: vec![],
+            signatures: vec![NodeSignature { node_id: 0, signature: vec![2; 96] }],
+            threshold_numerator: 1,
+            threshold_denominator: 3,
+            ciphersuite,
+        };
+        assert_eq!(RotationInputs::decode(&rotation.encode()), Ok(rotation));
+    }
+}
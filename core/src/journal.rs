@@ -0,0 +1,619 @@
+// Journals committed by the guest programs, ABI-encoded so contracts can `abi.decode` them.
+// `contracts/CommitmentVerification.sol` and the Forge tests mirror these structs field by field.
+//
+// Every journal is a `Journal` header wrapping the ABI encoding of one payload struct. The header
+// tells consumers which layout follows and under which modes it was produced, so journals stay
+// distinguishable as hashing and signature modes change. `flags` packs, least significant bit first:
+//   bit 0        hash mode (0: plain, 1: RFC 6962)
+//   bit 1        merkle mode (0: sorted, 1: indexed)
+//   bit 2        BLS variant (0: min-pk, 1: min-sig)
+//   bit 3        compact layout, set only in compact journals
+//   bit 4        private layout, the payload is `PrivatePublicInputs`
+//   bits 5-7     reserved, zero
+//   bits 8-15    signature scheme
+//   bits 16-31   depth of the proven path, 0 when there is none
+//
+// A compact journal drops the ABI encoding, the signature and every variable-length field, which
+// saves calldata and `sha256(journal)` gas on chain. It is a fixed 143-byte big-endian layout:
+//   0    u16   version
+//   2    u8    statement type
+//   3    u32   flags
+//   7    48    merkle root, or end running hash for record streams
+//   55   48    leaf, or record hash for record streams
+//   103  u64   leaf index, or record position for record streams
+//   111  32    signer hash, see below
+// Its first two bytes are the version, which ABI-encoded journals always start with zeros in place
+// of, so the two layouts cannot be confused. Compact journals only hold roots signed by a single BLS
+// key, whose signature scheme has no parameters. The signer hash folds in what the full journal
+// records about how the root was signed, so a compact journal binds the same key, DST and context:
+//   SHA-256(len(signer) (u32) || signer || bls_dst_hash || context_bound (u8)
+//           || len(domain) (u32) || domain || chain_id (u64) || epoch (u64))
+// with an empty domain and zero chain id and epoch when no context was bound.
+//
+// A private journal keeps the header but replaces the payload of an inclusion or record stream
+// statement with `PrivatePublicInputs`: the root, a salted commitment to the leaf, a digest of the
+// signer and the signer parameters, without the leaf, its position or the signature. The statement
+// type still names the statement that was proven. See `privacy.rs`.
+
+use alloc::vec::Vec;
+
+use alloy_sol_types::{sol, SolValue};
+use sha2::{Digest, Sha256};
+
+use crate::bls::BlsVariant;
+use crate::merkle::{HashMode, MerkleMode};
+use crate::message::SigningContext;
+
+/// Version of the journal layout written by this crate. Version 2 added the `nullifier` and `nullifier_domain` fields.
+pub const JOURNAL_VERSION: u16 = 2;
+
+/// Length of a compact journal.
+pub const COMPACT_JOURNAL_LEN: usize = 143;
+
+const FLAG_HASH_MODE: u32 = 1 << 0;
+const FLAG_MERKLE_MODE: u32 = 1 << 1;
+const FLAG_BLS_VARIANT: u32 = 1 << 2;
+const FLAG_COMPACT: u32 = 1 << 3;
+const FLAG_PRIVATE: u32 = 1 << 4;
+const FLAGS_RESERVED: u32 = 0xe0;
+const FLAGS_SCHEME_SHIFT: u32 = 8;
+const FLAGS_DEPTH_SHIFT: u32 = 16;
+
+sol! {
+    #![sol(all_derives)]
+
+    // Header every journal starts with.
+    struct Journal {
+        uint16 version;
+        uint8 statement_type; // see StatementType
+        uint32 flags;
+        bytes payload; // ABI-encoded payload struct for the statement type
+    }
+
+    // Journal of the main program for an inclusion statement.
+    struct PublicInputs {
+        bytes merkle_root;
+        bytes leaf;
+        bytes signer;
+        bytes signature;
+        uint32 depth;
+        uint8 merkle_mode;
+        uint64 leaf_index;
+        uint8 hash_mode;
+        uint8 signature_scheme;
+        uint8 bls_variant;
+        bytes bls_dst_hash;
+        bool context_bound;
+        bytes domain;
+        uint64 chain_id;
+        uint64 epoch;
+        bytes signer_params;
+        bytes nullifier; // privacy::nullifier of the leaf, empty unless requested
+        bytes nullifier_domain; // application the nullifier was derived for, empty without one
+    }
+
+    // Journal of the main program for a single-leaf statement in private mode.
+    struct PrivatePublicInputs {
+        bytes merkle_root; // end running hash for record streams
+        bytes leaf_commitment; // SHA-384(salt || leaf), of the record hash for record streams
+        bytes signer_hash; // see privacy::signer_digest
+        uint8 signature_scheme;
+        uint8 bls_variant;
+        bytes bls_dst_hash;
+        bool context_bound;
+        bytes domain;
+        uint64 chain_id;
+        uint64 epoch;
+        bytes signer_params;
+        bytes nullifier; // privacy::nullifier of the leaf, empty unless requested
+        bytes nullifier_domain; // application the nullifier was derived for, empty without one
+    }
+
+    // Journal of the main program for a multiproof statement.
+    struct MultiproofPublicInputs {
+        bytes merkle_root;
+        bytes[] leaves;
+        bytes signer;
+        bytes signature;
+        uint32 depth;
+        uint8 merkle_mode;
+        uint64[] leaf_indices;
+        uint8 hash_mode;
+        uint8 signature_scheme;
+        uint8 bls_variant;
+        bytes bls_dst_hash;
+        bool context_bound;
+        bytes domain;
+        uint64 chain_id;
+        uint64 epoch;
+        bytes signer_params;
+    }
+
+    // Journal of the main program for a sparse tree statement.
+    struct SparsePublicInputs {
+        bytes merkle_root;
+        bytes key;
+        bool absent;
+        bytes value;
+        bytes signer;
+        bytes signature;
+        uint32 depth;
+        uint8 hash_mode;
+        uint8 signature_scheme;
+        uint8 bls_variant;
+        bytes bls_dst_hash;
+        bool context_bound;
+        bytes domain;
+        uint64 chain_id;
+        uint64 epoch;
+        bytes signer_params;
+    }
+
+    // Journal of the main program for a record stream statement.
+    struct RecordStreamPublicInputs {
+        bytes start_running_hash;
+        bytes end_running_hash;
+        bytes record_hash;
+        bytes signer;
+        bytes signature;
+        uint32 position;
+        uint32 record_count;
+        uint8 signature_scheme;
+        uint8 bls_variant;
+        bytes bls_dst_hash;
+        bool context_bound;
+        bytes domain;
+        uint64 chain_id;
+        uint64 epoch;
+        bytes signer_params;
+        bytes nullifier; // privacy::nullifier of the leaf, empty unless requested
+        bytes nullifier_domain; // application the nullifier was derived for, empty without one
+    }
+
+    // Journal of the rotation program.
+    struct RotationPublicInputs {
+        bytes old_address_book_hash;
+        bytes new_address_book_hash;
+        uint64 threshold_numerator;
+        uint64 threshold_denominator;
+        uint64 signed_stake;
+        uint64 total_stake;
+        uint8 bls_variant;
+        bytes bls_dst_hash;
+    }
+
+    // Signer parameters committed for a record file signed by a node, the header fields its
+    // metadata hash covers besides the running hashes.
+    struct SignatureFileParams {
+        uint8 file_version;
+        uint32 hapi_major;
+        uint32 hapi_minor;
+        uint32 hapi_patch;
+        uint64 block_number; // 0 for version 5 files
+    }
+
+    // Signer parameters committed for a quorum of address book nodes.
+    struct QuorumParams {
+        uint64 threshold_numerator;
+        uint64 threshold_denominator;
+        uint64 signed_stake;
+        uint64 total_stake;
+    }
+
+    // Signer parameters committed for an aggregate signature from part of a committee.
+    struct AggregateParams {
+        uint32 committee_size;
+        bytes participation;
+        uint8 key_validation; // 0: proofs of possession checked, 1: registry committee
+    }
+
+    // Signer parameters committed for a threshold signature combined from shares, each checked
+    // against its signer's public key share: the bls::key_set_id of the group key, threshold and
+    // key shares.
+    struct ThresholdParams {
+        bytes key_set_id;
+    }
+}
+
+// Signature schemes recorded in the journal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureScheme {
+    Bls = 0,
+    RsaSignatureFile = 1, // params: SignatureFileParams
+    BlsQuorum = 2, // params: QuorumParams
+    BlsAggregate = 3, // params: AggregateParams
+    BlsThreshold = 4, // params: ThresholdParams
+}
+
+impl TryFrom<u8> for SignatureScheme {
+    type Error = &'static str;
+
+    fn try_from(scheme: u8) -> Result<Self, Self::Error> {
+        match scheme {
+            0 => Ok(SignatureScheme::Bls),
+            1 => Ok(SignatureScheme::RsaSignatureFile),
+            2 => Ok(SignatureScheme::BlsQuorum),
+            3 => Ok(SignatureScheme::BlsAggregate),
+            4 => Ok(SignatureScheme::BlsThreshold),
+            _ => Err("Unknown signature scheme"),
+        }
+    }
+}
+
+// Which journal layout the main program commits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JournalMode {
+    Full, // versioned header and ABI-encoded payload
+    Compact, // fixed layout, single-leaf statements only, DST and signing context folded into the signer hash
+    Private { salt: [u8; 32] }, // single-leaf statements only, leaf committed under salt, signer hashed
+}
+
+// Payload layouts, one per kind of statement the programs prove.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatementType {
+    Inclusion = 0, // PublicInputs
+    Multiproof = 1, // MultiproofPublicInputs
+    Sparse = 2, // SparsePublicInputs
+    RecordStream = 3, // RecordStreamPublicInputs
+    Rotation = 4, // RotationPublicInputs
+}
+
+/// Modes a journal was produced under, as packed into the header flags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JournalFlags {
+    pub hash_mode: HashMode,
+    pub merkle_mode: MerkleMode,
+    pub bls_variant: BlsVariant,
+    pub signature_scheme: SignatureScheme,
+    pub depth: u16,
+}
+
+impl JournalFlags {
+    pub fn pack(&self) -> u32 {
+        let mut flags =
+            (u32::from(self.depth) << FLAGS_DEPTH_SHIFT) | ((self.signature_scheme as u32) << FLAGS_SCHEME_SHIFT);
+        if self.hash_mode == HashMode::Rfc6962 {
+            flags |= FLAG_HASH_MODE;
+        }
+        if self.merkle_mode == MerkleMode::Indexed {
+            flags |= FLAG_MERKLE_MODE;
+        }
+        if self.bls_variant == BlsVariant::MinSig {
+            flags |= FLAG_BLS_VARIANT;
+        }
+        flags
+    }
+
+    pub fn unpack(flags: u32) -> Result<Self, &'static str> {
+        if flags & (FLAGS_RESERVED | FLAG_COMPACT | FLAG_PRIVATE) != 0 {
+            return Err("Reserved journal flags set");
+        }
+        Ok(JournalFlags {
+            hash_mode: if flags & FLAG_HASH_MODE != 0 { HashMode::Rfc6962 } else { HashMode::Plain },
+            merkle_mode: if flags & FLAG_MERKLE_MODE != 0 { MerkleMode::Indexed } else { MerkleMode::Sorted },
+            bls_variant: if flags & FLAG_BLS_VARIANT != 0 { BlsVariant::MinSig } else { BlsVariant::MinPk },
+            signature_scheme: SignatureScheme::try_from((flags >> FLAGS_SCHEME_SHIFT) as u8)?,
+            depth: (flags >> FLAGS_DEPTH_SHIFT) as u16,
+        })
+    }
+}
+
+/// The payload of a journal, tagged with its statement type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JournalPayload {
+    Inclusion(PublicInputs),
+    Multiproof(MultiproofPublicInputs),
+    Sparse(SparsePublicInputs),
+    RecordStream(RecordStreamPublicInputs),
+    Rotation(RotationPublicInputs),
+    Private(StatementType, PrivatePublicInputs), // Inclusion or RecordStream
+}
+
+impl JournalPayload {
+    pub fn statement_type(&self) -> StatementType {
+        match self {
+            JournalPayload::Inclusion(_) => StatementType::Inclusion,
+            JournalPayload::Multiproof(_) => StatementType::Multiproof,
+            JournalPayload::Sparse(_) => StatementType::Sparse,
+            JournalPayload::RecordStream(_) => StatementType::RecordStream,
+            JournalPayload::Rotation(_) => StatementType::Rotation,
+            JournalPayload::Private(statement_type, _) => *statement_type,
+        }
+    }
+
+    fn abi_encode(&self) -> Vec<u8> {
+        match self {
+            JournalPayload::Inclusion(payload) => payload.abi_encode(),
+            JournalPayload::Multiproof(payload) => payload.abi_encode(),
+            JournalPayload::Sparse(payload) => payload.abi_encode(),
+            JournalPayload::RecordStream(payload) => payload.abi_encode(),
+            JournalPayload::Rotation(payload) => payload.abi_encode(),
+            JournalPayload::Private(_, payload) => payload.abi_encode(),
+        }
+    }
+
+    fn abi_decode(statement_type: u8, data: &[u8]) -> Result<Self, &'static str> {
+        const INCLUSION: u8 = StatementType::Inclusion as u8;
+        const MULTIPROOF: u8 = StatementType::Multiproof as u8;
+        const SPARSE: u8 = StatementType::Sparse as u8;
+        const RECORD_STREAM: u8 = StatementType::RecordStream as u8;
+        const ROTATION: u8 = StatementType::Rotation as u8;
+
+        let payload = match statement_type {
+            INCLUSION => PublicInputs::abi_decode(data, true).map(JournalPayload::Inclusion),
+            MULTIPROOF => MultiproofPublicInputs::abi_decode(data, true).map(JournalPayload::Multiproof),
+            SPARSE => SparsePublicInputs::abi_decode(data, true).map(JournalPayload::Sparse),
+            RECORD_STREAM => RecordStreamPublicInputs::abi_decode(data, true).map(JournalPayload::RecordStream),
+            ROTATION => RotationPublicInputs::abi_decode(data, true).map(JournalPayload::Rotation),
+            _ => return Err("Unknown statement type"),
+        };
+        payload.map_err(|_| "Invalid journal payload")
+    }
+}
+
+/// A journal split into its header fields and payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedJournal {
+    pub flags: JournalFlags,
+    pub payload: JournalPayload,
+}
+
+/// Encodes a journal of the current version.
+pub fn encode_journal(flags: JournalFlags, payload: &JournalPayload) -> Vec<u8> {
+    let private = if matches!(payload, JournalPayload::Private(..)) { FLAG_PRIVATE } else { 0 };
+    Journal {
+        version: JOURNAL_VERSION,
+        statement_type: payload.statement_type() as u8,
+        flags: flags.pack() | private,
+        payload: payload.abi_encode().into(),
+    }
+    .abi_encode()
+}
+
+/// Decodes a journal, rejecting versions other than `JOURNAL_VERSION` and any malformed field.
+pub fn decode_journal(journal: &[u8]) -> Result<DecodedJournal, &'static str> {
+    let header = Journal::abi_decode(journal, true).map_err(|_| "Invalid journal header")?;
+    if header.version != JOURNAL_VERSION {
+        return Err("Unsupported journal version");
+    }
+    if header.flags & FLAG_PRIVATE == 0 {
+        return Ok(DecodedJournal {
+            flags: JournalFlags::unpack(header.flags)?,
+            payload: JournalPayload::abi_decode(header.statement_type, &header.payload)?,
+        });
+    }
+
+    let statement_type = match header.statement_type {
+        0 => StatementType::Inclusion,
+        3 => StatementType::RecordStream,
+        _ => return Err("Statement type has no private journal"),
+    };
+    let payload = PrivatePublicInputs::abi_decode(&header.payload, true).map_err(|_| "Invalid journal payload")?;
+    Ok(DecodedJournal {
+        flags: JournalFlags::unpack(header.flags & !FLAG_PRIVATE)?,
+        payload: JournalPayload::Private(statement_type, payload),
+    })
+}
+
+/// The fields of a compact journal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompactJournal {
+    pub statement_type: StatementType,
+    pub flags: JournalFlags,
+    pub root: [u8; 48],
+    pub leaf: [u8; 48],
+    pub leaf_index: u64,
+    pub signer_hash: [u8; 32],
+}
+
+impl CompactJournal {
+    /// Hashes a BLS public key, together with the DST hash and signing context the root was signed
+    /// under, into the compact `signer_hash`.
+    pub fn hash_signer(signer: &[u8], bls_dst_hash: &[u8; 48], context: Option<&SigningContext>) -> [u8; 32] {
+        let (domain, chain_id, epoch) = context.map_or((&[][..], 0, 0), |context| (&context.domain[..], context.chain_id, context.epoch));
+        Sha256::new()
+            .chain_update((signer.len() as u32).to_be_bytes())
+            .chain_update(signer)
+            .chain_update(bls_dst_hash)
+            .chain_update([context.is_some() as u8])
+            .chain_update((domain.len() as u32).to_be_bytes())
+            .chain_update(domain)
+            .chain_update(chain_id.to_be_bytes())
+            .chain_update(epoch.to_be_bytes())
+            .finalize()
+            .into()
+    }
+
+    /// Encodes a compact journal of the current version.
+    pub fn encode(&self) -> Vec<u8> {
+        [
+            &JOURNAL_VERSION.to_be_bytes()[..],
+            &[self.statement_type as u8],
+            &(self.flags.pack() | FLAG_COMPACT).to_be_bytes(),
+            &self.root,
+            &self.leaf,
+            &self.leaf_index.to_be_bytes(),
+            &self.signer_hash,
+        ]
+        .concat()
+    }
+
+    /// Decodes a compact journal, rejecting versions other than `JOURNAL_VERSION`.
+    pub fn decode(journal: &[u8]) -> Result<Self, &'static str> {
+        if journal.len() != COMPACT_JOURNAL_LEN {
+            return Err("Invalid compact journal length");
+        }
+        if u16::from_be_bytes([journal[0], journal[1]]) != JOURNAL_VERSION {
+            return Err("Unsupported journal version");
+        }
+        let statement_type = match journal[2] {
+            0 => StatementType::Inclusion,
+            3 => StatementType::RecordStream,
+            _ => return Err("Statement type has no compact journal"),
+        };
+        let flags = u32::from_be_bytes(journal[3..7].try_into().unwrap());
+        if flags & FLAG_COMPACT == 0 {
+            return Err("Compact flag not set");
+        }
+        let flags = JournalFlags::unpack(flags & !FLAG_COMPACT)?;
+        if flags.signature_scheme != SignatureScheme::Bls {
+            return Err("Signature scheme has no compact journal");
+        }
+        Ok(CompactJournal {
+            statement_type,
+            flags,
+            root: journal[7..55].try_into().unwrap(),
+            leaf: journal[55..103].try_into().unwrap(),
+            leaf_index: u64::from_be_bytes(journal[103..111].try_into().unwrap()),
+            signer_hash: journal[111..143].try_into().unwrap(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::Sha384;
+
+    use crate::privacy::{leaf_commitment, signer_digest};
+
+    use super::*;
+
+    #[test]
+    fn test_journal_header() {
+        let flags = JournalFlags {
+            hash_mode: HashMode::Rfc6962,
+            merkle_mode: MerkleMode::Indexed,
+            bls_variant: BlsVariant::MinSig,
+            signature_scheme: SignatureScheme::BlsQuorum,
+            depth: 384,
+        };
+        assert_eq!(flags.pack(), 0x0180_0207);
+        assert_eq!(JournalFlags::unpack(flags.pack()), Ok(flags));
+        assert_eq!(JournalFlags::unpack(1 << 4), Err("Reserved journal flags set"));
+        assert_eq!(JournalFlags::unpack(5 << 8), Err("Unknown signature scheme"));
+
+        let payload = JournalPayload::Inclusion(PublicInputs {
+            merkle_root: vec![1; 48].into(),
+            leaf: vec![2; 48].into(),
+            depth: 384,
+            leaf_index: 5,
+            ..Default::default()
+        });
+        let journal = encode_journal(flags, &payload);
+        assert_eq!(decode_journal(&journal), Ok(DecodedJournal { flags, payload }));
+
+        // The header is ABI-encoded behind a tuple offset: version, statement type, then flags words.
+        let patched = |word: usize, value: u8| {
+            let mut journal = journal.clone();
+            journal[32 * word + 31] = value;
+            journal
+        };
+        assert_eq!(decode_journal(&patched(1, 1)), Err("Unsupported journal version"));
+        assert_eq!(decode_journal(&patched(2, 9)), Err("Unknown statement type"));
+        assert_eq!(decode_journal(&patched(3, 0x20)), Err("Reserved journal flags set"));
+        assert_eq!(decode_journal(&journal[..64]), Err("Invalid journal header"));
+    }
+
+    #[test]
+    fn test_compact_journal() {
+        let flags = JournalFlags {
+            hash_mode: HashMode::Plain,
+            merkle_mode: MerkleMode::Sorted,
+            bls_variant: BlsVariant::MinPk,
+            signature_scheme: SignatureScheme::Bls,
+            depth: 256,
+        };
+        let signer = [0xaf; 48];
+        let dst_hash = [0xd5; 48];
+        let signer_hash = CompactJournal::hash_signer(&signer, &dst_hash, None);
+        let journal = CompactJournal {
+            statement_type: StatementType::Inclusion,
+            flags,
+            root: [1; 48],
+            leaf: [2; 48],
+            leaf_index: 0,
+            signer_hash,
+        };
+        let encoded = journal.encode();
+        let expected = [
+            &[0, 2, 0][..],
+            &[0x01, 0x00, 0x00, 0x08],
+            &[1; 48],
+            &[2; 48],
+            &[0; 8],
+            &signer_hash,
+        ]
+        .concat();
+        assert_eq!(encoded, expected);
+
+        // The signer hash binds the DST and signing context the full journal would have recorded.
+        let preimage = [&48u32.to_be_bytes()[..], &signer, &dst_hash, &[0], &[0; 4], &[0; 16]].concat();
+        assert_eq!(signer_hash, <[u8; 32]>::from(Sha256::digest(preimage)));
+        let context = SigningContext { domain: b"hedera-zk/state-root".to_vec(), chain_id: 295, epoch: 7 };
+        let bound = CompactJournal::hash_signer(&signer, &dst_hash, Some(&context));
+        assert_ne!(bound, signer_hash);
+        assert_ne!(CompactJournal::hash_signer(&signer, &dst_hash, Some(&SigningContext { epoch: 8, ..context.clone() })), bound);
+        assert_ne!(CompactJournal::hash_signer(&signer, &[0xd6; 48], None), signer_hash);
+        assert_ne!(CompactJournal::hash_signer(&signer, &dst_hash, Some(&SigningContext { domain: Vec::new(), chain_id: 0, epoch: 0 })), signer_hash);
+        assert_eq!(encoded.len(), COMPACT_JOURNAL_LEN);
+        assert_eq!(CompactJournal::decode(&encoded), Ok(journal));
+
+        // Full journals never parse as compact ones, and compact journals must carry the compact flag.
+        let full = encode_journal(flags, &JournalPayload::Inclusion(PublicInputs::default()));
+        assert!(CompactJournal::decode(&full[..COMPACT_JOURNAL_LEN]).is_err());
+        let mut unflagged = encoded.clone();
+        unflagged[6] = 0;
+        assert_eq!(CompactJournal::decode(&unflagged), Err("Compact flag not set"));
+        let mut multiproof = encoded.clone();
+        multiproof[2] = StatementType::Multiproof as u8;
+        assert_eq!(CompactJournal::decode(&multiproof), Err("Statement type has no compact journal"));
+        // The signer hash does not cover signer parameters, so only single keys have a compact journal.
+        let mut quorum = encoded.clone();
+        quorum[5] = SignatureScheme::BlsQuorum as u8;
+        assert_eq!(CompactJournal::decode(&quorum), Err("Signature scheme has no compact journal"));
+        assert_eq!(CompactJournal::decode(&encoded[1..]), Err("Invalid compact journal length"));
+    }
+
+    #[test]
+    fn test_private_journal() {
+        let salt = [7; 32];
+        let leaf = [2; 48];
+        let commitment = leaf_commitment(&salt, &leaf);
+        assert_eq!(commitment, <[u8; 48]>::from(Sha384::new().chain_update(salt).chain_update(leaf).finalize()));
+        assert_ne!(commitment, leaf_commitment(&[8; 32], &leaf));
+
+        // Public keys are hashed, signers that already are 48-byte hashes are kept.
+        let pubkey = [0xaf; 48];
+        assert_eq!(signer_digest(SignatureScheme::Bls, &pubkey), <[u8; 48]>::from(Sha384::digest(pubkey)));
+        assert_eq!(signer_digest(SignatureScheme::BlsQuorum, &pubkey), pubkey);
+
+        let flags = JournalFlags {
+            hash_mode: HashMode::Plain,
+            merkle_mode: MerkleMode::Sorted,
+            bls_variant: BlsVariant::MinPk,
+            signature_scheme: SignatureScheme::Bls,
+            depth: 256,
+        };
+        let payload = JournalPayload::Private(
+            StatementType::Inclusion,
+            PrivatePublicInputs {
+                merkle_root: vec![1; 48].into(),
+                leaf_commitment: commitment.to_vec().into(),
+                signer_hash: signer_digest(SignatureScheme::Bls, &pubkey).to_vec().into(),
+                ..Default::default()
+            },
+        );
+        let journal = encode_journal(flags, &payload);
+        assert_eq!(payload.statement_type(), StatementType::Inclusion);
+        assert_eq!(decode_journal(&journal), Ok(DecodedJournal { flags, payload }));
+
+        // The private flag sits in the flags word, and only single-leaf statements have a private layout.
+        assert_eq!(journal[32 * 3 + 31], 0x10);
+        let mut multiproof = journal.clone();
+        multiproof[32 * 2 + 31] = StatementType::Multiproof as u8;
+        assert_eq!(decode_journal(&multiproof), Err("Statement type has no private journal"));
+        let mut unflagged = journal.clone();
+        unflagged[32 * 3 + 31] = 0;
+        assert!(decode_journal(&unflagged).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+// Types and verification logic shared by the guest programs and the host: private inputs and their
+// wire format, Merkle and record stream hashing, BLS verification, the signed message, the
+// journals and the commitments private journals carry. Keeping them in one crate means native and
+// in-guest verification cannot drift apart.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod address_book;
+pub mod bls;
+pub mod inputs;
+pub mod journal;
+pub mod merkle;
+pub mod message;
+pub mod privacy;
+pub mod record_stream;
+pub mod sig_file;
+pub mod sparse;
@@ -0,0 +1,230 @@
+// Merkle path verification.
+
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha384};
+
+// Maximum number of siblings accepted in a Merkle path.
+pub const MAX_MERKLE_DEPTH: usize = 256;
+
+/// How a node and its sibling are ordered before they are hashed together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MerkleMode {
+    /// The smaller hash goes first. Proofs do not bind the position of the leaf.
+    Sorted = 0,
+    /// The node goes left or right according to the leaf index, least significant bit first.
+    Indexed = 1,
+}
+
+/// How leaves and internal nodes are hashed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashMode {
+    /// Bare SHA-384 for both leaves and nodes.
+    Plain = 0,
+    /// RFC 6962 domain separation: SHA-384(0x00 || leaf) and SHA-384(0x01 || left || right).
+    Rfc6962 = 1,
+}
+
+const RFC6962_LEAF_PREFIX: u8 = 0x00;
+const RFC6962_NODE_PREFIX: u8 = 0x01;
+
+/// Hashes a leaf into the bottom level of the tree.
+pub fn hash_leaf(hash_mode: HashMode, leaf: &[u8]) -> [u8; 48] {
+    let mut hasher = Sha384::new();
+    if hash_mode == HashMode::Rfc6962 {
+        hasher.update([RFC6962_LEAF_PREFIX]);
+    }
+    hasher.update(leaf);
+    hasher.finalize().into()
+}
+
+/// Hashes two ordered children into their parent node.
+pub fn hash_node(hash_mode: HashMode, left: &[u8], right: &[u8]) -> [u8; 48] {
+    let mut hasher = Sha384::new();
+    if hash_mode == HashMode::Rfc6962 {
+        hasher.update([RFC6962_NODE_PREFIX]);
+    }
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn hash_pair(
+    mode: MerkleMode,
+    hash_mode: HashMode,
+    node: &[u8],
+    sibling: &[u8],
+    node_is_right: bool,
+) -> [u8; 48] {
+    let node_first = match mode {
+        MerkleMode::Sorted => node < sibling,
+        MerkleMode::Indexed => !node_is_right,
+    };
+
+    if node_first {
+        hash_node(hash_mode, node, sibling)
+    } else {
+        hash_node(hash_mode, sibling, node)
+    }
+}
+
+/// Returns true if `leaf_index` addresses a leaf of a tree with `depth` levels.
+pub fn leaf_index_in_range(depth: usize, leaf_index: u64) -> bool {
+    depth >= 64 || leaf_index >> depth == 0
+}
+
+// Function to verify a Merkle proof
+pub fn compute_merkle_root<S: AsRef<[u8]>>(
+    mode: MerkleMode,
+    hash_mode: HashMode,
+    leaf: &[u8],
+    leaf_index: u64,
+    merkle_path: &[S],
+) -> [u8; 48] {
+    let mut hash = hash_leaf(hash_mode, leaf);
+
+    for (level, sibling) in merkle_path.iter().enumerate() {
+        let node_is_right = level < 64 && (leaf_index >> level) & 1 == 1;
+        hash = hash_pair(mode, hash_mode, &hash, sibling.as_ref(), node_is_right);
+    }
+
+    hash
+}
+
+/// Computes the root of a tree from several of its leaves at once.
+///
+/// `leaf_indices` must be strictly increasing and within a tree of `depth` levels. `proof` holds only
+/// the nodes that cannot be derived from the leaves, ordered bottom-up and left to right within each
+/// level. Only `Indexed` trees are accepted: in `Sorted` mode the indices would describe the shape of
+/// the proof without being bound by it. Returns `None` if the proof is malformed, has nodes left over
+/// or the tree is sorted.
+pub fn compute_multiproof_root<L: AsRef<[u8]>, S: AsRef<[u8]>>(
+    mode: MerkleMode,
+    hash_mode: HashMode,
+    depth: usize,
+    leaves: &[L],
+    leaf_indices: &[u64],
+    proof: &[S],
+) -> Option<[u8; 48]> {
+    if mode != MerkleMode::Indexed || leaves.is_empty() || leaves.len() != leaf_indices.len() {
+        return None;
+    }
+    if leaf_indices.windows(2).any(|pair| pair[0] >= pair[1])
+        || !leaf_indices.iter().all(|&index| leaf_index_in_range(depth, index))
+    {
+        return None;
+    }
+
+    let mut nodes: Vec<(u64, [u8; 48])> = leaf_indices
+        .iter()
+        .zip(leaves)
+        .map(|(&index, leaf)| (index, hash_leaf(hash_mode, leaf.as_ref())))
+        .collect();
+    let mut proof = proof.iter();
+
+    for _ in 0..depth {
+        let mut parents = Vec::with_capacity(nodes.len());
+        let mut i = 0;
+        while i < nodes.len() {
+            let (index, hash) = nodes[i];
+            let sibling = match nodes.get(i + 1) {
+                Some(&(next_index, next_hash)) if next_index == index ^ 1 => {
+                    i += 1;
+                    next_hash
+                }
+                _ => proof.next()?.as_ref().try_into().ok()?,
+            };
+            parents.push((index >> 1, hash_pair(mode, hash_mode, &hash, &sibling, index & 1 == 1)));
+            i += 1;
+        }
+        nodes = parents;
+    }
+
+    match (proof.next(), nodes.as_slice()) {
+        (None, [(_, root)]) => Some(*root),
+        _ => None,
+    }
+}
+
+/// RFC 6962 Merkle tree hash of a whole list of leaves, which need not be a power of two long.
+///
+/// The tree splits at the largest power of two below the leaf count, so for a power-of-two count
+/// the result matches `compute_merkle_root` in `Indexed` mode with RFC 6962 hashing.
+pub fn merkle_tree_hash<S: AsRef<[u8]>>(leaves: &[S]) -> [u8; 48] {
+    match leaves.len() {
+        0 => Sha384::digest([]).into(),
+        1 => hash_leaf(HashMode::Rfc6962, leaves[0].as_ref()),
+        count => {
+            let split = 1 << (usize::BITS - 1 - (count - 1).leading_zeros());
+            hash_node(HashMode::Rfc6962, &merkle_tree_hash(&leaves[..split]), &merkle_tree_hash(&leaves[split..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::{Digest, Sha384};
+
+    use super::*;
+
+    #[test]
+    fn test_indexed_merkle_path() {
+        for (hash_mode, leaf_prefix, node_prefix) in [(HashMode::Plain, &[][..], &[][..]), (HashMode::Rfc6962, &[0u8][..], &[1u8][..])] {
+            let leaves: Vec<[u8; 48]> = (0u8..4).map(|i| Sha384::digest([i]).into()).collect();
+            let hashed: Vec<[u8; 48]> = leaves.iter().map(|leaf| Sha384::new().chain_update(leaf_prefix).chain_update(leaf).finalize().into()).collect();
+            let node = |l: &[u8; 48], r: &[u8; 48]| -> [u8; 48] { Sha384::new().chain_update(node_prefix).chain_update(l).chain_update(r).finalize().into() };
+            let left = node(&hashed[0], &hashed[1]);
+            let right = node(&hashed[2], &hashed[3]);
+            let root = node(&left, &right);
+
+            assert_eq!(compute_merkle_root(MerkleMode::Indexed, hash_mode, &leaves[0], 0, &[hashed[1], right]), root);
+            assert_eq!(compute_merkle_root(MerkleMode::Indexed, hash_mode, &leaves[2], 2, &[hashed[3], left]), root);
+            assert_eq!(compute_merkle_root(MerkleMode::Indexed, hash_mode, &leaves[3], 3, &[hashed[2], left]), root);
+            // The same siblings at the wrong position must not reproduce the root.
+            assert_ne!(compute_merkle_root(MerkleMode::Indexed, hash_mode, &leaves[0], 1, &[hashed[1], right]), root);
+        }
+    }
+
+    #[test]
+    fn test_multiproof() {
+        let leaves: Vec<[u8; 48]> = (0u8..8).map(|i| Sha384::digest([i]).into()).collect();
+        let paths: Vec<Vec<[u8; 48]>> = {
+            // Build every level of a full depth-3 tree so single-leaf paths can be read off it.
+            let mut levels = vec![leaves.iter().map(|leaf| Sha384::digest(leaf).into()).collect::<Vec<[u8; 48]>>()];
+            while levels.last().unwrap().len() > 1 {
+                let level = levels.last().unwrap();
+                levels.push(level.chunks(2).map(|pair| Sha384::new().chain_update(pair[0]).chain_update(pair[1]).finalize().into()).collect());
+            }
+            (0..8).map(|i| (0..3).map(|level| levels[level][(i >> level) ^ 1]).collect()).collect()
+        };
+        let root = compute_merkle_root(MerkleMode::Indexed, HashMode::Plain, &leaves[1], 1, &paths[1]);
+
+        // Leaves 2 and 3 are siblings and leaf 1 shares their grandparent, so two proof nodes suffice.
+        let proof = [paths[1][0], paths[1][2]];
+        let multiproof_root = compute_multiproof_root(MerkleMode::Indexed, HashMode::Plain, 3, &leaves[1..4], &[1, 2, 3], &proof);
+        assert_eq!(multiproof_root, Some(root));
+
+        // A single leaf reduces to an ordinary path.
+        assert_eq!(compute_multiproof_root(MerkleMode::Indexed, HashMode::Plain, 3, &leaves[5..6], &[5], &paths[5]), Some(root));
+
+        // Extra proof nodes, unsorted indices and wrong positions are all rejected.
+        assert_eq!(compute_multiproof_root(MerkleMode::Indexed, HashMode::Plain, 3, &leaves[5..6], &[5], &[paths[5][0], paths[5][1], paths[5][2], root]), None);
+        assert_eq!(compute_multiproof_root(MerkleMode::Indexed, HashMode::Plain, 3, &leaves[1..4], &[1, 3, 2], &proof), None);
+        assert_ne!(compute_multiproof_root(MerkleMode::Indexed, HashMode::Plain, 3, &leaves[1..4], &[0, 1, 2], &proof), Some(root));
+
+        // Sorted trees do not bind positions, so the indices a multiproof would commit to mean nothing.
+        assert_eq!(compute_multiproof_root(MerkleMode::Sorted, HashMode::Plain, 3, &leaves[5..6], &[5], &paths[5]), None);
+    }
+
+    #[test]
+    fn test_committee_root() {
+        let leaves: Vec<[u8; 48]> = (0..4u8).map(|i| Sha384::digest([i]).into()).collect();
+
+        // A power-of-two committee hashes like an indexed RFC 6962 tree.
+        let path = [hash_leaf(HashMode::Rfc6962, &leaves[3]), hash_node(HashMode::Rfc6962, &hash_leaf(HashMode::Rfc6962, &leaves[0]), &hash_leaf(HashMode::Rfc6962, &leaves[1]))];
+        assert_eq!(merkle_tree_hash(&leaves), compute_merkle_root(MerkleMode::Indexed, HashMode::Rfc6962, &leaves[2], 2, &path));
+
+        // Otherwise the tree splits at the largest power of two below the size.
+        assert_eq!(merkle_tree_hash(&leaves[..3]), hash_node(HashMode::Rfc6962, &merkle_tree_hash(&leaves[..2]), &hash_leaf(HashMode::Rfc6962, &leaves[2])));
+    }
+}
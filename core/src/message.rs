@@ -0,0 +1,52 @@
+// Construction of the message that signers sign over a root.
+//
+// A root signed on its own is valid for any network, round or purpose. Binding it to a context
+// makes the signed message `domain || chain_id (8 bytes, big-endian) || epoch (8 bytes, big-endian)
+// || root`. Every field after the domain has a fixed size, so the encoding is unambiguous.
+
+use alloc::vec::Vec;
+
+/// Where and when a root was signed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningContext {
+    pub domain: Vec<u8>, // purpose of the signature, e.g. b"hedera-zk/state-root"
+    pub chain_id: u64,
+    pub epoch: u64, // round or epoch the root belongs to
+}
+
+/// Builds the message signed over `root`, which is the bare root when there is no context.
+pub fn signed_message(context: Option<&SigningContext>, root: &[u8; 48]) -> Vec<u8> {
+    match context {
+        None => root.to_vec(),
+        Some(context) => [
+            context.domain.as_slice(),
+            &context.chain_id.to_be_bytes(),
+            &context.epoch.to_be_bytes(),
+            root,
+        ]
+        .concat(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_message() {
+        let root = [0xab; 48];
+        assert_eq!(signed_message(None, &root), root);
+
+        let context = SigningContext { domain: b"hedera-zk/state-root".to_vec(), chain_id: 295, epoch: 7 };
+        let message = signed_message(Some(&context), &root);
+        assert_eq!(message.len(), context.domain.len() + 8 + 8 + 48);
+        assert!(message.starts_with(b"hedera-zk/state-root"));
+        assert!(message.ends_with(&root));
+
+        // Signatures over one network or round do not verify for another.
+        let other_chain = SigningContext { chain_id: 296, ..context.clone() };
+        let other_epoch = SigningContext { epoch: 8, ..context.clone() };
+        assert_ne!(signed_message(Some(&other_chain), &root), message);
+        assert_ne!(signed_message(Some(&other_epoch), &root), message);
+    }
+}
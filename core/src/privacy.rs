@@ -0,0 +1,71 @@
+// Values committed in place of the leaf and signer key when a proof must not reveal them.
+//
+// The leaf commitment is `SHA-384(salt || leaf)` with a 32-byte salt chosen by the prover, so the
+// leaf cannot be recovered by hashing candidate leaves. Whoever knows the salt can open it later.
+//
+// A nullifier is `SHA-256(leaf || secret || domain)`. Leaves that can be claimed commit to their
+// secret as `SHA-384(secret || data)`, which the guest checks, so a leaf has exactly one secret and
+// every proof about it for the same application yields the same nullifier. A contract that records
+// nullifiers, and checks the domain committed next to them, can then accept each claim once without
+// learning the leaf. SHA-256 keeps it a single `bytes32` on chain. The leaf and secret have fixed
+// sizes, so the domain needs no length prefix.
+
+use sha2::{Digest, Sha256, Sha384};
+
+use crate::journal::SignatureScheme;
+
+/// Commits to a leaf under a secret salt.
+pub fn leaf_commitment(salt: &[u8; 32], leaf: &[u8; 48]) -> [u8; 48] {
+    Sha384::new().chain_update(salt).chain_update(leaf).finalize().into()
+}
+
+/// The signer as committed by private journals. Raw public keys are replaced by their SHA-384 hash;
+/// the other schemes already record a 48-byte hash of a key or of a key registry, which is kept.
+pub fn signer_digest(scheme: SignatureScheme, signer: &[u8]) -> [u8; 48] {
+    match scheme {
+        SignatureScheme::Bls | SignatureScheme::BlsThreshold => Sha384::digest(signer).into(),
+        SignatureScheme::RsaSignatureFile | SignatureScheme::BlsQuorum | SignatureScheme::BlsAggregate => {
+            signer.try_into().expect("Signer is not a 48-byte hash")
+        }
+    }
+}
+
+/// The leaf of a tree that holds `data` for whoever knows `secret`.
+pub fn claimable_leaf(secret: &[u8; 32], data: &[u8]) -> [u8; 48] {
+    Sha384::new().chain_update(secret).chain_update(data).finalize().into()
+}
+
+/// Derives the nullifier of a leaf for the application `domain`.
+pub fn nullifier(leaf: &[u8; 48], secret: &[u8; 32], domain: &[u8]) -> [u8; 32] {
+    Sha256::new().chain_update(leaf).chain_update(secret).chain_update(domain).finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nullifier() {
+        let leaf = [2; 48];
+        let secret = [5; 32];
+        let expected: [u8; 32] = sha2::Sha256::new().chain_update(leaf).chain_update(secret).chain_update(b"app").finalize().into();
+        assert_eq!(nullifier(&leaf, &secret, b"app"), expected);
+        // A claim is tied to the leaf, the secret and the application.
+        assert_ne!(nullifier(&[3; 48], &secret, b"app"), expected);
+        assert_ne!(nullifier(&leaf, &[6; 32], b"app"), expected);
+        assert_ne!(nullifier(&leaf, &secret, b"other-app"), expected);
+    }
+
+    #[test]
+    fn test_repeated_claim() {
+        let secret = [5; 32];
+        let leaf = claimable_leaf(&secret, b"account 0.0.1234, 100 tokens");
+        assert_eq!(leaf, <[u8; 48]>::from(Sha384::new().chain_update(secret).chain_update(b"account 0.0.1234, 100 tokens").finalize()));
+
+        // A second claim on the same leaf must use the secret the leaf commits to, and so gives the
+        // same nullifier. Any other secret fails the leaf check before a nullifier is derived.
+        let first = nullifier(&leaf, &secret, b"app");
+        assert_eq!(nullifier(&leaf, &secret, b"app"), first);
+        assert_ne!(claimable_leaf(&[6; 32], b"account 0.0.1234, 100 tokens"), leaf);
+    }
+}
@@ -0,0 +1,68 @@
+// Hedera record stream running-hash chain.
+//
+// A record file starts from the running hash left by the previous file and folds in the SHA-384
+// hash of each record stream object in order, with both hashes serialized as Hash objects:
+//   running_hash = SHA-384(Hash running_hash || Hash object_hash)
+// where Hash = `long class id || int class version (1) || int digest type (SHA-384) || int length (48) || hash`,
+// all integers big-endian. The running hash after the last object is the one the file ends with.
+
+use sha2::{Digest, Sha384};
+
+pub(crate) const HASH_CLASS_ID: u64 = 0xf422da83a251741e;
+pub(crate) const HASH_CLASS_VERSION: i32 = 1;
+pub(crate) const SHA384_DIGEST_TYPE: i32 = 0x58ff811b;
+
+/// Serializes a SHA-384 hash as a Hash object.
+pub fn hash_object(hash: &[u8; 48]) -> [u8; 68] {
+    let mut object = [0; 68];
+    object[..8].copy_from_slice(&HASH_CLASS_ID.to_be_bytes());
+    object[8..12].copy_from_slice(&HASH_CLASS_VERSION.to_be_bytes());
+    object[12..16].copy_from_slice(&SHA384_DIGEST_TYPE.to_be_bytes());
+    object[16..20].copy_from_slice(&48i32.to_be_bytes());
+    object[20..].copy_from_slice(hash);
+    object
+}
+
+/// Folds one record stream object hash into the running hash.
+pub fn next_running_hash(running_hash: &[u8; 48], object_hash: &[u8; 48]) -> [u8; 48] {
+    Sha384::new()
+        .chain_update(hash_object(running_hash))
+        .chain_update(hash_object(object_hash))
+        .finalize()
+        .into()
+}
+
+/// Recomputes the running hash at the end of `object_hashes`, starting from `start_running_hash`.
+pub fn compute_running_hash(start_running_hash: &[u8; 48], object_hashes: &[[u8; 48]]) -> [u8; 48] {
+    object_hashes
+        .iter()
+        .fold(*start_running_hash, |running_hash, object_hash| next_running_hash(&running_hash, object_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use sha2::{Digest, Sha384};
+
+    use super::*;
+
+    #[test]
+    fn test_running_hash_chain() {
+        let start: [u8; 48] = Sha384::digest(b"previous file").into();
+        let records: Vec<[u8; 48]> = (0u8..3).map(|i| Sha384::digest([i]).into()).collect();
+
+        let hash_object = |hash: &[u8; 48]| {
+            [&0xf422da83a251741eu64.to_be_bytes()[..], &1i32.to_be_bytes(), &0x58ff811bi32.to_be_bytes(), &48i32.to_be_bytes(), hash].concat()
+        };
+        let mut expected = start;
+        for record in &records {
+            expected = Sha384::new().chain_update(hash_object(&expected)).chain_update(hash_object(record)).finalize().into();
+        }
+        assert_eq!(compute_running_hash(&start, &records), expected);
+
+        // Reordering records changes the end running hash.
+        let swapped = [records[1], records[0], records[2]];
+        assert_ne!(compute_running_hash(&start, &swapped), expected);
+    }
+}
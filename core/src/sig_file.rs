@@ -0,0 +1,506 @@
+// Parsers for Hedera record stream signature files (`.rcd_sig`) and the header and running hashes
+// of the record files (`.rcd`) they sign. Signature checking itself lives in the guest.
+//
+// A signature file signs two hashes of its record file. The entire file hash is SHA-384 over the
+// whole record file, which a proof would have to carry. The metadata hash only covers the header
+// and the start and end running hashes, so it is what ties the signature to a running hash chain:
+//   version 5: SHA-384(int 5 || int hapi major || int hapi minor || int hapi patch
+//                      || int object stream version (1) || Hash start || Hash end)
+//   version 6: SHA-384(int 6 || int hapi major || int hapi minor || int hapi patch
+//                      || start (48 bytes) || end (48 bytes) || long block number)
+// with the Hash objects serialized as in `record_stream.rs`.
+//
+// Version 5 files are the version byte followed by Java-serialized objects, all integers big-endian:
+//   int   object stream signature version (1)
+//   Hash  entire file hash
+//   Sig   entire file signature
+//   Hash  metadata hash
+//   Sig   metadata signature
+// with Hash serialized as in `record_stream.rs` and
+// Sig = `long class id, int class version, int signature type, int length, int checksum, signature`.
+//
+// Version 6 files are the version byte followed by a `SignatureFile` protobuf message whose
+// `file_signature` (field 1) and `metadata_signature` (field 2) are each a `SignatureObject { type = 1,
+// length = 2, checksum = 3, signature = 4, hash_object = 5 }` and `HashObject { algorithm = 1,
+// length = 2, hash = 3 }`.
+//
+// Record files start with an int version. Version 5 record files continue with the ints and start
+// Hash their metadata hash covers, then the record stream objects, and end with the end Hash.
+// Version 6 record files continue with a `RecordStreamFile` protobuf message: `hapi_proto_version`
+// (field 1, a `SemanticVersion { major = 1, minor = 2, patch = 3 }`), `start_object_running_hash`
+// (2) and `end_object_running_hash` (4) as `HashObject`s, and `block_number` (5), among others.
+
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha384};
+
+use crate::record_stream::{hash_object, HASH_CLASS_ID, SHA384_DIGEST_TYPE};
+
+const OBJECT_STREAM_SIGNATURE_VERSION: i32 = 1;
+const OBJECT_STREAM_VERSION: i32 = 1;
+const SIGNATURE_CLASS_ID: u64 = 0x13dc4b399b245c69;
+const SHA384_WITH_RSA: u64 = 1;
+const SHA384_HASH_ALGORITHM: u64 = 1;
+
+/// The parts of a signature file needed to authenticate a record file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureFile {
+    pub version: u8,
+    pub file_hash: [u8; 48],
+    /// SHA384withRSA signature over `file_hash`.
+    pub file_signature: Vec<u8>,
+    pub metadata_hash: [u8; 48],
+    /// SHA384withRSA signature over `metadata_hash`.
+    pub metadata_signature: Vec<u8>,
+}
+
+/// Header fields of a record file that its metadata hash covers besides the running hashes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordFileHeader {
+    pub hapi_version: [u32; 3], // major, minor, patch
+    pub block_number: u64, // version 6 only, ignored for version 5
+}
+
+/// The parts of a record file that its metadata hash covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordFile {
+    pub version: u8,
+    pub header: RecordFileHeader,
+    pub start_running_hash: [u8; 48],
+    pub end_running_hash: [u8; 48],
+}
+
+/// Recomputes the metadata hash of a version 5 or 6 record file. Returns `None` for other versions.
+pub fn metadata_hash(
+    version: u8,
+    header: &RecordFileHeader,
+    start_running_hash: &[u8; 48],
+    end_running_hash: &[u8; 48],
+) -> Option<[u8; 48]> {
+    let mut hasher = Sha384::new();
+    hasher.update(i32::from(version).to_be_bytes());
+    for part in header.hapi_version {
+        hasher.update(part.to_be_bytes());
+    }
+    match version {
+        5 => {
+            hasher.update(OBJECT_STREAM_VERSION.to_be_bytes());
+            hasher.update(hash_object(start_running_hash));
+            hasher.update(hash_object(end_running_hash));
+        }
+        6 => {
+            hasher.update(start_running_hash);
+            hasher.update(end_running_hash);
+            hasher.update(header.block_number.to_be_bytes());
+        }
+        _ => return None,
+    }
+    Some(hasher.finalize().into())
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], &'static str> {
+        if len > self.bytes.len() {
+            return Err("File truncated");
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, &'static str> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i32(&mut self) -> Result<i32, &'static str> {
+        Ok(i32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, &'static str> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, &'static str> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn varint(&mut self) -> Result<u64, &'static str> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Malformed protobuf varint")
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+/// Parses a version 5 or version 6 signature file.
+pub fn parse_signature_file(bytes: &[u8]) -> Result<SignatureFile, &'static str> {
+    let mut reader = Reader { bytes };
+    let version = reader.u8()?;
+    let ((file_hash, file_signature), (metadata_hash, metadata_signature)) = match version {
+        5 => parse_v5(&mut reader)?,
+        6 => parse_v6(reader.bytes)?,
+        _ => return Err("Unsupported signature file version"),
+    };
+
+    Ok(SignatureFile { version, file_hash, file_signature, metadata_hash, metadata_signature })
+}
+
+/// Parses the header and running hashes of a version 5 or version 6 record file.
+pub fn parse_record_file(bytes: &[u8]) -> Result<RecordFile, &'static str> {
+    let mut reader = Reader { bytes };
+    match reader.i32()? {
+        5 => parse_record_file_v5(&mut reader),
+        6 => parse_record_file_v6(reader.bytes),
+        _ => Err("Unsupported record file version"),
+    }
+}
+
+fn parse_record_file_v5(reader: &mut Reader) -> Result<RecordFile, &'static str> {
+    let hapi_version = [reader.u32()?, reader.u32()?, reader.u32()?];
+    if reader.i32()? != OBJECT_STREAM_VERSION {
+        return Err("Unsupported object stream version");
+    }
+    let start_running_hash = read_v5_hash(reader)?;
+    // The record stream objects run up to the end running hash, the last Hash in the file.
+    let end = reader.bytes.len().checked_sub(68).ok_or("File truncated")?;
+    let end_running_hash = read_v5_hash(&mut Reader { bytes: &reader.bytes[end..] })?;
+
+    Ok(RecordFile {
+        version: 5,
+        header: RecordFileHeader { hapi_version, block_number: 0 },
+        start_running_hash,
+        end_running_hash,
+    })
+}
+
+fn parse_record_file_v6(message: &[u8]) -> Result<RecordFile, &'static str> {
+    let (mut hapi_version, mut block_number) = (None, 0);
+    let (mut start_running_hash, mut end_running_hash) = (None, None);
+    for_each_field(message, |field, value| {
+        match (field, value) {
+            (1, ProtoValue::Bytes(bytes)) => hapi_version = Some(parse_v6_semantic_version(bytes)?),
+            (2, ProtoValue::Bytes(bytes)) => start_running_hash = Some(parse_v6_hash(bytes)?),
+            (4, ProtoValue::Bytes(bytes)) => end_running_hash = Some(parse_v6_hash(bytes)?),
+            (5, ProtoValue::Varint(v)) => block_number = v,
+            _ => {}
+        }
+        Ok(())
+    })?;
+
+    Ok(RecordFile {
+        version: 6,
+        header: RecordFileHeader { hapi_version: hapi_version.ok_or("Missing HAPI version")?, block_number },
+        start_running_hash: start_running_hash.ok_or("Missing start running hash")?,
+        end_running_hash: end_running_hash.ok_or("Missing end running hash")?,
+    })
+}
+
+fn parse_v6_semantic_version(semantic_version: &[u8]) -> Result<[u32; 3], &'static str> {
+    let mut version = [0u32; 3];
+    for_each_field(semantic_version, |field, value| {
+        if let (1..=3, ProtoValue::Varint(v)) = (field, value) {
+            version[field as usize - 1] = u32::try_from(v).map_err(|_| "Invalid HAPI version")?;
+        }
+        Ok(())
+    })?;
+    Ok(version)
+}
+
+// A hash and the signature over it.
+type SignedHash = ([u8; 48], Vec<u8>);
+
+fn parse_v5(reader: &mut Reader) -> Result<(SignedHash, SignedHash), &'static str> {
+    if reader.i32()? != OBJECT_STREAM_SIGNATURE_VERSION {
+        return Err("Unsupported object stream signature version");
+    }
+    let file = (read_v5_hash(reader)?, read_v5_signature(reader)?);
+    let metadata = (read_v5_hash(reader)?, read_v5_signature(reader)?);
+    if !reader.is_empty() {
+        return Err("Trailing bytes in signature file");
+    }
+
+    Ok((file, metadata))
+}
+
+fn read_v5_hash(reader: &mut Reader) -> Result<[u8; 48], &'static str> {
+    if reader.u64()? != HASH_CLASS_ID {
+        return Err("Unexpected hash class id");
+    }
+    reader.i32()?; // class version
+    if reader.i32()? != SHA384_DIGEST_TYPE || reader.i32()? != 48 {
+        return Err("Hash is not SHA-384");
+    }
+    Ok(reader.take(48)?.try_into().unwrap())
+}
+
+fn read_v5_signature(reader: &mut Reader) -> Result<Vec<u8>, &'static str> {
+    if reader.u64()? != SIGNATURE_CLASS_ID {
+        return Err("Unexpected signature class id");
+    }
+    reader.i32()?; // class version
+    if reader.i32()? != SHA384_WITH_RSA as i32 {
+        return Err("Signature is not SHA384withRSA");
+    }
+    let length = reader.i32()?;
+    if length < 0 || reader.i32()? != 101 - length {
+        return Err("Signature checksum mismatch");
+    }
+    Ok(reader.take(length as usize)?.to_vec())
+}
+
+// Visits every field of a protobuf message, handing varints and length-delimited fields to `visit`.
+fn for_each_field<'a>(
+    bytes: &'a [u8],
+    mut visit: impl FnMut(u64, ProtoValue<'a>) -> Result<(), &'static str>,
+) -> Result<(), &'static str> {
+    let mut reader = Reader { bytes };
+    while !reader.is_empty() {
+        let key = reader.varint()?;
+        let value = match key & 0x7 {
+            0 => ProtoValue::Varint(reader.varint()?),
+            1 => reader.take(8).map(|_| ProtoValue::Skipped)?,
+            2 => {
+                let length = reader.varint()?;
+                ProtoValue::Bytes(reader.take(usize::try_from(length).map_err(|_| "Field too long")?)?)
+            }
+            5 => reader.take(4).map(|_| ProtoValue::Skipped)?,
+            _ => return Err("Unsupported protobuf wire type"),
+        };
+        visit(key >> 3, value)?;
+    }
+    Ok(())
+}
+
+enum ProtoValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    Skipped,
+}
+
+fn parse_v6(message: &[u8]) -> Result<(SignedHash, SignedHash), &'static str> {
+    let (mut file_signature, mut metadata_signature) = (None, None);
+    for_each_field(message, |field, value| {
+        match (field, value) {
+            (1, ProtoValue::Bytes(bytes)) => file_signature = Some(bytes),
+            (2, ProtoValue::Bytes(bytes)) => metadata_signature = Some(bytes),
+            _ => {}
+        }
+        Ok(())
+    })?;
+
+    Ok((
+        parse_v6_signature(file_signature.ok_or("Missing file signature")?)?,
+        parse_v6_signature(metadata_signature.ok_or("Missing metadata signature")?)?,
+    ))
+}
+
+fn parse_v6_signature(signature_object: &[u8]) -> Result<SignedHash, &'static str> {
+    let (mut signature_type, mut length, mut checksum) = (0, 0, 0);
+    let (mut signature, mut hash_object) = (None, None);
+    for_each_field(signature_object, |field, value| {
+        match (field, value) {
+            (1, ProtoValue::Varint(v)) => signature_type = v,
+            (2, ProtoValue::Varint(v)) => length = v as i32,
+            (3, ProtoValue::Varint(v)) => checksum = v as i32,
+            (4, ProtoValue::Bytes(bytes)) => signature = Some(bytes),
+            (5, ProtoValue::Bytes(bytes)) => hash_object = Some(bytes),
+            _ => {}
+        }
+        Ok(())
+    })?;
+    let signature = signature.ok_or("Missing signature bytes")?;
+    if signature_type != SHA384_WITH_RSA {
+        return Err("Signature is not SHA384withRSA");
+    }
+    if length as usize != signature.len() || checksum != 101 - length {
+        return Err("Signature checksum mismatch");
+    }
+
+    Ok((parse_v6_hash(hash_object.ok_or("Missing hash object")?)?, signature.to_vec()))
+}
+
+fn parse_v6_hash(hash_object: &[u8]) -> Result<[u8; 48], &'static str> {
+    let (mut algorithm, mut hash) = (0, None);
+    for_each_field(hash_object, |field, value| {
+        match (field, value) {
+            (1, ProtoValue::Varint(v)) => algorithm = v,
+            (3, ProtoValue::Bytes(bytes)) => hash = Some(bytes),
+            _ => {}
+        }
+        Ok(())
+    })?;
+    let hash = hash.ok_or("Missing signed hash")?;
+    if algorithm != SHA384_HASH_ALGORITHM || hash.len() != 48 {
+        return Err("Hash is not SHA-384");
+    }
+
+    Ok(hash.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_signature_files() {
+        let file_hash = [7u8; 48];
+        let signature = vec![9u8; 384];
+
+        // Version 5: Java-serialized hash and signature objects.
+        let hash_object = |hash: &[u8]| [&0xf422da83a251741eu64.to_be_bytes()[..], &1i32.to_be_bytes(), &0x58ff811bi32.to_be_bytes(), &48i32.to_be_bytes(), hash].concat();
+        let signature_object = |sig: &[u8], checksum: i32| [&0x13dc4b399b245c69u64.to_be_bytes()[..], &1i32.to_be_bytes(), &1i32.to_be_bytes(), &(sig.len() as i32).to_be_bytes(), &checksum.to_be_bytes(), sig].concat();
+        let v5 = [&[5u8][..], &1i32.to_be_bytes(), &hash_object(&file_hash), &signature_object(&signature, 101 - 384), &hash_object(&[8u8; 48]), &signature_object(&[10u8; 384], 101 - 384)].concat();
+
+        let parsed = parse_signature_file(&v5).unwrap();
+        assert_eq!((parsed.version, parsed.file_hash, &parsed.file_signature), (5, file_hash, &signature));
+        assert_eq!((parsed.metadata_hash, parsed.metadata_signature), ([8u8; 48], vec![10u8; 384]));
+
+        let bad_checksum = [&[5u8][..], &1i32.to_be_bytes(), &hash_object(&file_hash), &signature_object(&signature, 0), &hash_object(&[8u8; 48]), &signature_object(&signature, 101 - 384)].concat();
+        assert!(parse_signature_file(&bad_checksum).is_err());
+
+        // Version 6: a SignatureFile protobuf message.
+        fn varint(mut value: u64) -> Vec<u8> {
+            let mut out = Vec::new();
+            while value >= 0x80 {
+                out.push(value as u8 | 0x80);
+                value >>= 7;
+            }
+            out.push(value as u8);
+            out
+        }
+        let bytes_field = |field: u64, payload: &[u8]| [varint(field << 3 | 2), varint(payload.len() as u64), payload.to_vec()].concat();
+        let varint_field = |field: u64, value: i64| [varint(field << 3), varint(value as u64)].concat();
+        let proto_signature = |hash: &[u8], sig: &[u8]| {
+            let proto_hash = [varint_field(1, 1), varint_field(2, 48), bytes_field(3, hash)].concat();
+            [varint_field(1, 1), varint_field(2, 384), varint_field(3, 101 - 384), bytes_field(4, sig), bytes_field(5, &proto_hash)].concat()
+        };
+        let v6 = [vec![6u8], bytes_field(1, &proto_signature(&file_hash, &signature)), bytes_field(2, &proto_signature(&[8u8; 48], &[10u8; 384]))].concat();
+
+        let parsed = parse_signature_file(&v6).unwrap();
+        assert_eq!((parsed.version, parsed.file_hash, &parsed.file_signature), (6, file_hash, &signature));
+        assert_eq!((parsed.metadata_hash, parsed.metadata_signature), ([8u8; 48], vec![10u8; 384]));
+
+        assert!(parse_signature_file(&v6[..v6.len() - 1]).is_err());
+        let unsigned_metadata = [vec![6u8], bytes_field(1, &proto_signature(&file_hash, &signature))].concat();
+        assert_eq!(parse_signature_file(&unsigned_metadata), Err("Missing metadata signature"));
+    }
+
+    #[test]
+    fn test_metadata_hash() {
+        let header = RecordFileHeader { hapi_version: [0, 47, 1], block_number: 1000 };
+        let (start, end) = ([1u8; 48], [2u8; 48]);
+        let ints = |ints: &[i32]| ints.iter().flat_map(|int| int.to_be_bytes()).collect::<Vec<u8>>();
+
+        let hash_object = |hash: &[u8]| [&0xf422da83a251741eu64.to_be_bytes()[..], &ints(&[1, 0x58ff811b, 48]), hash].concat();
+        let v5 = [ints(&[5, 0, 47, 1, 1]), hash_object(&start), hash_object(&end)].concat();
+        assert_eq!(metadata_hash(5, &header, &start, &end), Some(Sha384::digest(v5).into()));
+        // Version 5 files have no block number.
+        assert_eq!(metadata_hash(5, &RecordFileHeader { block_number: 0, ..header }, &start, &end), metadata_hash(5, &header, &start, &end));
+
+        let v6 = [ints(&[6, 0, 47, 1]), start.to_vec(), end.to_vec(), 1000u64.to_be_bytes().to_vec()].concat();
+        assert_eq!(metadata_hash(6, &header, &start, &end), Some(Sha384::digest(v6).into()));
+        assert_ne!(metadata_hash(6, &header, &end, &start), metadata_hash(6, &header, &start, &end));
+        assert_eq!(metadata_hash(4, &header, &start, &end), None);
+    }
+
+    #[test]
+    fn test_parse_record_files() {
+        let header = RecordFileHeader { hapi_version: [0, 47, 1], block_number: 1000 };
+        let (start, end) = ([1u8; 48], [2u8; 48]);
+        let ints = |ints: &[i32]| ints.iter().flat_map(|int| int.to_be_bytes()).collect::<Vec<u8>>();
+
+        // Version 5: the end running hash follows the record stream objects.
+        let hash_object = |hash: &[u8; 48]| crate::record_stream::hash_object(hash).to_vec();
+        let v5 = [ints(&[5, 0, 47, 1, 1]), hash_object(&start), vec![0xee; 100], hash_object(&end)].concat();
+        let parsed = parse_record_file(&v5).unwrap();
+        assert_eq!(parsed, RecordFile { version: 5, header: RecordFileHeader { block_number: 0, ..header }, start_running_hash: start, end_running_hash: end });
+        assert!(parse_record_file(&v5[..v5.len() - 1]).is_err());
+
+        // Version 6: a RecordStreamFile protobuf message, in which zero fields are left out.
+        fn varint(mut value: u64) -> Vec<u8> {
+            let mut out = Vec::new();
+            while value >= 0x80 {
+                out.push(value as u8 | 0x80);
+                value >>= 7;
+            }
+            out.push(value as u8);
+            out
+        }
+        let bytes_field = |field: u64, payload: &[u8]| [varint(field << 3 | 2), varint(payload.len() as u64), payload.to_vec()].concat();
+        let varint_field = |field: u64, value: u64| [varint(field << 3), varint(value)].concat();
+        let proto_hash = |hash: &[u8]| [varint_field(1, 1), varint_field(2, 48), bytes_field(3, hash)].concat();
+        let v6 = [
+            ints(&[6]),
+            bytes_field(1, &[varint_field(2, 47), varint_field(3, 1)].concat()),
+            bytes_field(2, &proto_hash(&start)),
+            bytes_field(3, b"record stream item"),
+            bytes_field(4, &proto_hash(&end)),
+            varint_field(5, 1000),
+        ]
+        .concat();
+        assert_eq!(parse_record_file(&v6), Ok(RecordFile { version: 6, header, start_running_hash: start, end_running_hash: end }));
+        let unfinished = [ints(&[6]), bytes_field(1, &varint_field(2, 47)), bytes_field(2, &proto_hash(&start))].concat();
+        assert_eq!(parse_record_file(&unfinished), Err("Missing end running hash"));
+        assert_eq!(parse_record_file(&ints(&[7])), Err("Unsupported record file version"));
+    }
+
+    // Checks that a signature file signs its record file: the entire file hash, the metadata hash
+    // recomputed from the record file, and both RSA signatures under the node key.
+    fn check_signed_record_file(record_file: &[u8], signature_file: &[u8], node_key: &[u8]) -> RecordFile {
+        use rsa::{pkcs1v15::{Signature, VerifyingKey}, pkcs8::DecodePublicKey, signature::Verifier, RsaPublicKey};
+
+        let record = parse_record_file(record_file).unwrap();
+        let signature = parse_signature_file(signature_file).unwrap();
+        assert_eq!(signature.version, record.version);
+        assert_eq!(signature.file_hash, <[u8; 48]>::from(Sha384::digest(record_file)));
+        assert_eq!(
+            metadata_hash(record.version, &record.header, &record.start_running_hash, &record.end_running_hash),
+            Some(signature.metadata_hash)
+        );
+
+        let key = VerifyingKey::<Sha384>::new(RsaPublicKey::from_public_key_der(node_key).unwrap());
+        for (hash, signed) in [(signature.file_hash, &signature.file_signature), (signature.metadata_hash, &signature.metadata_signature)] {
+            key.verify(&hash, &Signature::try_from(signed.as_slice()).unwrap()).unwrap();
+        }
+        record
+    }
+
+    #[test]
+    fn test_signed_record_file_fixture() {
+        // A synthetic version 6 record file and its signature file, see fixtures/README.md. They are
+        // not mainnet files.
+        let record = check_signed_record_file(
+            include_bytes!("../fixtures/synthetic_v6.rcd"),
+            include_bytes!("../fixtures/synthetic_v6.rcd_sig"),
+            include_bytes!("../fixtures/synthetic_node_key.der"),
+        );
+        let start: [u8; 48] = Sha384::digest(b"synthetic start running hash").into();
+        let records: Vec<[u8; 48]> = (0u8..3).map(|i| Sha384::digest([i]).into()).collect();
+
+        assert_eq!(record.header, RecordFileHeader { hapi_version: [0, 47, 0], block_number: 1000 });
+        assert_eq!(record.start_running_hash, start);
+        assert_eq!(record.end_running_hash, crate::record_stream::compute_running_hash(&start, &records));
+    }
+
+    #[test]
+    #[ignore = "needs a mainnet record file, its signature file and the node key in fixtures/mainnet, see fixtures/README.md"]
+    fn test_mainnet_record_file() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/mainnet");
+        let read = |name: &str| std::fs::read(dir.join(name)).unwrap_or_else(|_| panic!("Missing fixtures/mainnet/{}", name));
+
+        check_signed_record_file(&read("record.rcd"), &read("record.rcd_sig"), &read("node_key.der"));
+    }
+}
@@ -0,0 +1,94 @@
+// Sparse Merkle tree verification.
+//
+// Every key addresses a fixed slot: the bits of the key, most significant first, pick the path from
+// the root. Empty slots hold `EMPTY_LEAF`, and an empty subtree hashes to the default node for its
+// height, so a proof only has to carry the siblings that are not default.
+
+use alloc::vec::Vec;
+
+use crate::merkle::{hash_leaf, hash_node, HashMode};
+
+// Maximum number of levels, one per bit of a SHA-384 sized key.
+pub const MAX_SPARSE_DEPTH: usize = 384;
+
+/// Value of an empty slot.
+pub const EMPTY_LEAF: [u8; 48] = [0u8; 48];
+
+/// Hashes an occupied slot, binding the value to its key.
+pub fn hash_sparse_leaf(hash_mode: HashMode, key: &[u8], value: &[u8]) -> [u8; 48] {
+    let mut preimage = Vec::with_capacity(key.len() + value.len());
+    preimage.extend_from_slice(key);
+    preimage.extend_from_slice(value);
+    hash_leaf(hash_mode, &preimage)
+}
+
+fn key_bit(key: &[u8; 48], bit: usize) -> bool {
+    key[bit / 8] >> (7 - bit % 8) & 1 == 1
+}
+
+/// Computes the root of a sparse tree from the slot addressed by `key`.
+///
+/// `value` is `None` to prove the slot is empty. `siblings` runs from the leaf level up to the level
+/// below the root, and `None` stands for the default node at that height. The depth of the tree is
+/// the number of siblings, and only the first `depth` bits of the key are used.
+/// Returns `None` if the tree is deeper than `MAX_SPARSE_DEPTH`.
+pub fn compute_sparse_root<S: AsRef<[u8]>>(
+    hash_mode: HashMode,
+    key: &[u8; 48],
+    value: Option<&[u8]>,
+    siblings: &[Option<S>],
+) -> Option<[u8; 48]> {
+    let depth = siblings.len();
+    if depth > MAX_SPARSE_DEPTH {
+        return None;
+    }
+
+    let mut hash = match value {
+        Some(value) => hash_sparse_leaf(hash_mode, key, value),
+        None => EMPTY_LEAF,
+    };
+    let mut default_node = EMPTY_LEAF;
+
+    for (height, sibling) in siblings.iter().enumerate() {
+        let sibling = match sibling {
+            Some(sibling) => sibling.as_ref(),
+            None => &default_node[..],
+        };
+        hash = if key_bit(key, depth - 1 - height) {
+            hash_node(hash_mode, sibling, &hash)
+        } else {
+            hash_node(hash_mode, &hash, sibling)
+        };
+        default_node = hash_node(hash_mode, &default_node, &default_node);
+    }
+
+    Some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::{Digest, Sha384};
+
+    use super::*;
+
+    #[test]
+    fn test_sparse_non_inclusion() {
+        let hash = |parts: &[&[u8]]| -> [u8; 48] { parts.iter().fold(Sha384::new(), |h, p| h.chain_update(p)).finalize().into() };
+        let no_siblings: [Option<[u8; 48]>; 2] = [None, None];
+
+        // Depth 2 tree holding only key A in the leftmost slot; key B sits in the third slot.
+        let key_a = [0u8; 48];
+        let mut key_b = [0u8; 48];
+        key_b[0] = 0b1000_0000;
+        let value: [u8; 48] = Sha384::digest(b"frozen").into();
+
+        let empty_subtree = hash(&[&EMPTY_LEAF, &EMPTY_LEAF]);
+        let left = hash(&[&hash(&[&key_a, &value]), &EMPTY_LEAF]);
+        let root = hash(&[&left, &empty_subtree]);
+
+        assert_eq!(compute_sparse_root(HashMode::Plain, &key_a, Some(&value), &no_siblings), Some(root));
+        assert_eq!(compute_sparse_root(HashMode::Plain, &key_b, None, &[None, Some(left)]), Some(root));
+        // Key A cannot be shown absent with the default siblings.
+        assert_ne!(compute_sparse_root(HashMode::Plain, &key_a, None, &no_siblings), Some(root));
+    }
+}
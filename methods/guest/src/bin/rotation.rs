@@ -0,0 +1,66 @@
+#![no_main]
+
+// Proves the hand-over from one address book to the next: nodes of the old book holding more than
+// the threshold fraction of its stake signed the hash of the new book. Chaining these receipts
+// carries trust from a known address book to the current one without a trusted update key.
+
+use std::io::Read;
+
+use risc0_zkvm::guest::env;
+
+use guests::signature::{address_book_hash, verify_quorum};
+use hedera_zk_core::inputs::RotationInputs;
+use hedera_zk_core::journal::{
+    encode_journal, JournalFlags, JournalPayload, RotationPublicInputs, SignatureScheme,
+};
+use hedera_zk_core::merkle::{HashMode, MerkleMode};
+
+risc0_zkvm::guest::entry!(main);
+fn main() {
+
+    let start = env::cycle_count();
+
+    let mut input = Vec::new();
+    env::stdin().read_to_end(&mut input).expect("Failed to read input");
+    let inputs = RotationInputs::decode(&input).expect("Invalid input encoding");
+    assert!(inputs.ciphersuite.dst_is_valid(), "Invalid BLS domain separation tag");
+
+    let new_address_book_hash = address_book_hash(inputs.ciphersuite.variant, &inputs.new_address_book);
+
+    println!("new address book hash: {:?}", new_address_book_hash);
+
+    let (old_address_book_hash, quorum) = verify_quorum(
+        &inputs.ciphersuite,
+        &new_address_book_hash,
+        &inputs.old_address_book,
+        &inputs.signatures,
+        inputs.threshold_numerator,
+        inputs.threshold_denominator,
+        false,
+    );
+
+    let public_inputs = RotationPublicInputs {
+        old_address_book_hash: old_address_book_hash.to_vec().into(),
+        new_address_book_hash: new_address_book_hash.to_vec().into(),
+        threshold_numerator: quorum.threshold_numerator,
+        threshold_denominator: quorum.threshold_denominator,
+        signed_stake: quorum.signed_stake,
+        total_stake: quorum.total_stake,
+        bls_variant: inputs.ciphersuite.variant as u8,
+        bls_dst_hash: inputs.ciphersuite.dst_hash().to_vec().into(),
+    };
+    // No path is proven, so the modes and depth in the flags are placeholders.
+    let flags = JournalFlags {
+        hash_mode: HashMode::Plain,
+        merkle_mode: MerkleMode::Sorted,
+        bls_variant: inputs.ciphersuite.variant,
+        signature_scheme: SignatureScheme::BlsQuorum,
+        depth: 0,
+    };
+    // Commit to the public values of the program.
+    env::commit_slice(&encode_journal(flags, &JournalPayload::Rotation(public_inputs)));
+
+    let end = env::cycle_count();
+    eprintln!("total cycle count: {}", end - start);
+
+}
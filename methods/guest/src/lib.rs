@@ -0,0 +1,3 @@
+// Guest-only parts of the programs. Everything the host needs as well lives in `hedera-zk-core`.
+
+pub mod signature;
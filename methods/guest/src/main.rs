@@ -1,96 +1,265 @@
 #![no_main]
 
-use sha2::{Sha384, Digest};
-use alloy_primitives::FixedBytes;
-use bls_signatures::{PublicKey, Signature, verify_messages, Serialize};
-
-use alloy_sol_types::{sol, SolValue};
-
-sol! {
-    struct PublicInputs {
-        bytes merkle_root;
-        bytes leaf;
-        bytes bls_pubkey;
-        bytes bls_signature;
-    }
-}
+use std::io::Read;
 
 use risc0_zkvm::guest::env;
-use serde::Deserialize;
 
-// Function to verify a Merkle proof
-fn compute_merkle_root(leaf: FixedBytes<48>, merkle_path: [[u8; 48]; 256]) -> [u8; 48] {
-    let mut hash = Sha384::digest(leaf.as_slice());
-    
-    for sibling in merkle_path {
-        let sibling_slice = sibling.as_slice();
-        let mut combined = Vec::with_capacity(96);
-        if hash.as_slice() < sibling_slice {
-            combined.extend_from_slice(&hash);
-            combined.extend_from_slice(&sibling_slice);
-        } else {
-            combined.extend_from_slice(&sibling_slice);
-            combined.extend_from_slice(&hash);
-        }
-        hash = Sha384::digest(&combined);
-    }
-    
-    let mut result = [0u8; 48];
-    result.copy_from_slice(hash.as_slice());
-    result
-}
-
-#[derive(Debug, Deserialize)]
-struct PrivateInputs {
-    pub merkle_root: FixedBytes<48>,
-    pub leaf: FixedBytes<48>,
-    pub bls_pubkey: FixedBytes<48>, // BLS public key size will always be 48 bytes
-    pub bls_signature: FixedBytes<96>, // BLS signature size will always be 96 bytes
-    pub serialized_path: FixedBytes<12288> // 48 * 256 length
-}
+use guests::signature::verify_root_signature;
+use hedera_zk_core::inputs::{PrivateInputs, Statement};
+use hedera_zk_core::journal::{
+    encode_journal, CompactJournal, JournalFlags, JournalMode, JournalPayload,
+    MultiproofPublicInputs, PublicInputs, RecordStreamPublicInputs, SparsePublicInputs,
+    PrivatePublicInputs, SignatureScheme, StatementType,
+};
+use hedera_zk_core::merkle::{
+    compute_merkle_root, compute_multiproof_root, leaf_index_in_range, MerkleMode, MAX_MERKLE_DEPTH,
+};
+use hedera_zk_core::privacy::{claimable_leaf, leaf_commitment, nullifier, signer_digest};
+use hedera_zk_core::record_stream::compute_running_hash;
+use hedera_zk_core::sparse::compute_sparse_root;
 
 risc0_zkvm::guest::entry!(main);
 fn main() {
 
     let start = env::cycle_count();
 
-    let private_inputs = env::read::<PrivateInputs>();
+    let mut input = Vec::new();
+    env::stdin().read_to_end(&mut input).expect("Failed to read input");
+    let private_inputs = PrivateInputs::decode(&input).expect("Invalid input encoding");
 
-    let merkle_path: [[u8; 48]; 256] = private_inputs.serialized_path.chunks_exact(48).map(|chunk| {
-        let mut arr = [0u8; 48];
-        arr.copy_from_slice(chunk);
-        arr
-    }).collect::<Vec<[u8; 48]>>().try_into().unwrap();
+    // Private journals hide the leaf and the signer key, so they must not end up in the logs either.
+    let private = matches!(private_inputs.journal_mode, JournalMode::Private { .. });
+    if !private {
+        println!("private inputs: {:?}", private_inputs);
+    }
 
+    let merkle_mode = private_inputs.merkle_mode;
+    let hash_mode = private_inputs.hash_mode;
+    let ciphersuite = &private_inputs.ciphersuite;
+    let context = private_inputs.context.as_ref();
+    assert!(ciphersuite.dst_is_valid(), "Invalid BLS domain separation tag");
 
-    println!("private inputs: {:?}", private_inputs);
+    let computed_root: [u8; 48] = match &private_inputs.statement {
+        Statement::Inclusion { leaf, leaf_index, merkle_path } => {
+            let depth = merkle_path.len();
+            assert!(depth <= MAX_MERKLE_DEPTH, "Merkle path too deep: {} > {}", depth, MAX_MERKLE_DEPTH);
+            match merkle_mode {
+                MerkleMode::Sorted => assert_eq!(*leaf_index, 0, "Leaf index is not bound in Sorted mode"),
+                MerkleMode::Indexed => assert!(leaf_index_in_range(depth, *leaf_index), "Leaf index out of range"),
+            }
 
-    let computed_root: [u8; 48] = compute_merkle_root(private_inputs.leaf, merkle_path);
-    
-    println!("computed_root: {:?}", computed_root);
+            compute_merkle_root(merkle_mode, hash_mode, leaf.as_slice(), *leaf_index, merkle_path)
+        }
+        Statement::Multiproof { depth, leaves, leaf_indices, proof } => {
+            assert_eq!(merkle_mode, MerkleMode::Indexed, "Multiproofs commit to leaf positions");
+            let depth = *depth as usize;
+            assert!(depth <= MAX_MERKLE_DEPTH, "Merkle path too deep: {} > {}", depth, MAX_MERKLE_DEPTH);
 
-    assert_eq!(computed_root, private_inputs.merkle_root.as_slice());
+            compute_multiproof_root(merkle_mode, hash_mode, depth, leaves, leaf_indices, proof)
+                .expect("Invalid multiproof")
+        }
+        Statement::Sparse { key, value, siblings } => {
+            assert_eq!(merkle_mode, MerkleMode::Indexed, "Sparse trees are position-indexed");
 
-    // Verify the BLS signature
-    let pubkey = PublicKey::from_bytes(&private_inputs.bls_pubkey.as_slice()).expect("Invalid public key");
-    let signature = Signature::from_bytes(&private_inputs.bls_signature.as_slice()).expect("Invalid signature");
+            compute_sparse_root(hash_mode, key, value.as_ref().map(|value| value.as_slice()), siblings)
+                .expect("Sparse tree too deep")
+        }
+        Statement::RecordStream { start_running_hash, record_hashes, position } => {
+            assert!((*position as usize) < record_hashes.len(), "Record position out of range");
 
-    println!("PublicKey: {:?}", private_inputs.bls_pubkey);
-    println!("Signature: {:?}", private_inputs.bls_signature);
+            compute_running_hash(start_running_hash, record_hashes)
+        }
+    };
     
-    assert!(verify_messages(&signature, &[computed_root.as_slice()], &[pubkey]), "Invalid verification");
+    if !private {
+        println!("computed_root: {:?}", computed_root);
+    }
+
+    assert_eq!(computed_root, private_inputs.merkle_root);
+
+    // Verify the root signature once for every leaf under the root
+    let signature_start = env::cycle_count();
+    let start_running_hash = match &private_inputs.statement {
+        Statement::RecordStream { start_running_hash, .. } => Some(start_running_hash),
+        _ => None,
+    };
+    let signer = verify_root_signature(
+        &computed_root,
+        start_running_hash,
+        context,
+        &private_inputs.root_signature,
+        ciphersuite,
+        private,
+    );
+    eprintln!("signature verification cycle count: {}", env::cycle_count() - signature_start);
 
     // Encocde the public values of the program.
-    let public_inputs: PublicInputs = PublicInputs {
-        merkle_root: computed_root.to_vec().into(),
-        leaf: private_inputs.leaf.to_vec().into(),
-        bls_pubkey: private_inputs.bls_pubkey.to_vec().into(),
-        bls_signature: private_inputs.bls_signature.to_vec().into()
+    let depth = match &private_inputs.statement {
+        Statement::Inclusion { merkle_path, .. } => merkle_path.len(),
+        Statement::Multiproof { depth, .. } => *depth as usize,
+        Statement::Sparse { siblings, .. } => siblings.len(),
+        Statement::RecordStream { .. } => 0,
+    };
+    let flags = JournalFlags {
+        hash_mode,
+        merkle_mode,
+        bls_variant: ciphersuite.variant,
+        signature_scheme: signer.scheme,
+        depth: depth as u16,
+    };
+    // How the root was signed, which every payload records the same way.
+    let signature_scheme = signer.scheme as u8;
+    let bls_variant = ciphersuite.variant as u8;
+    let bls_dst_hash = ciphersuite.dst_hash().to_vec();
+    let (context_bound, domain, chain_id, epoch) = match context {
+        Some(context) => (true, context.domain.clone(), context.chain_id, context.epoch),
+        None => (false, Vec::new(), 0, 0),
+    };
+    let (nullifier, nullifier_domain) = match &private_inputs.nullifier {
+        Some(inputs) => {
+            let leaf = match &private_inputs.statement {
+                Statement::Inclusion { leaf, .. } => leaf,
+                Statement::RecordStream { record_hashes, position, .. } => &record_hashes[*position as usize],
+                _ => panic!("Nullifiers need a single leaf"),
+            };
+            // The message must not carry the leaf, which private journals hide.
+            assert!(&claimable_leaf(&inputs.secret, &inputs.data) == leaf, "Leaf does not commit to the nullifier secret");
+            (nullifier(leaf, &inputs.secret, &inputs.domain).to_vec(), inputs.domain.clone())
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+    let journal = match private_inputs.journal_mode {
+        JournalMode::Compact => {
+            assert!(private_inputs.nullifier.is_none(), "Compact journals have no nullifier");
+            // The signer hash has no room for signer parameters, which only single keys go without.
+            assert_eq!(signer.scheme, SignatureScheme::Bls, "Compact journals only hold single-key BLS signatures");
+            let (statement_type, leaf, leaf_index) = match private_inputs.statement {
+                Statement::Inclusion { leaf, leaf_index, .. } => (StatementType::Inclusion, leaf, leaf_index),
+                Statement::RecordStream { record_hashes, position, .. } => {
+                    (StatementType::RecordStream, record_hashes[position as usize], u64::from(position))
+                }
+                _ => panic!("Compact journals only hold a single leaf"),
+            };
+            CompactJournal {
+                statement_type,
+                flags,
+                root: computed_root,
+                leaf,
+                leaf_index,
+                signer_hash: CompactJournal::hash_signer(&signer.signer, &ciphersuite.dst_hash(), context),
+            }
+            .encode()
+        }
+        JournalMode::Private { salt } => {
+            let (statement_type, leaf) = match private_inputs.statement {
+                Statement::Inclusion { leaf, .. } => (StatementType::Inclusion, leaf),
+                Statement::RecordStream { record_hashes, position, .. } => {
+                    (StatementType::RecordStream, record_hashes[position as usize])
+                }
+                _ => panic!("Private journals only hold a single leaf"),
+            };
+            let payload = PrivatePublicInputs {
+                merkle_root: computed_root.to_vec().into(),
+                leaf_commitment: leaf_commitment(&salt, &leaf).to_vec().into(),
+                signer_hash: signer_digest(signer.scheme, &signer.signer).to_vec().into(),
+                signature_scheme,
+                bls_variant,
+                bls_dst_hash: bls_dst_hash.into(),
+                context_bound,
+                domain: domain.into(),
+                chain_id,
+                epoch,
+                signer_params: signer.params.into(),
+                nullifier: nullifier.into(),
+                nullifier_domain: nullifier_domain.into(),
+            };
+            encode_journal(flags, &JournalPayload::Private(statement_type, payload))
+        }
+        JournalMode::Full => {
+            let payload = match private_inputs.statement {
+                Statement::Inclusion { leaf, leaf_index, merkle_path } => JournalPayload::Inclusion(PublicInputs {
+                    merkle_root: computed_root.to_vec().into(),
+                    leaf: leaf.to_vec().into(),
+                    signer: signer.signer.into(),
+                    signature: signer.signature.into(),
+                    depth: merkle_path.len() as u32,
+                    merkle_mode: merkle_mode as u8,
+                    leaf_index,
+                    hash_mode: hash_mode as u8,
+                    signature_scheme,
+                    bls_variant,
+                    bls_dst_hash: bls_dst_hash.into(),
+                    context_bound,
+                    domain: domain.into(),
+                    chain_id,
+                    epoch,
+                    signer_params: signer.params.into(),
+                    nullifier: nullifier.into(),
+                    nullifier_domain: nullifier_domain.into(),
+                }),
+                Statement::Multiproof { depth, leaves, leaf_indices, .. } => JournalPayload::Multiproof(MultiproofPublicInputs {
+                    merkle_root: computed_root.to_vec().into(),
+                    leaves: leaves.iter().map(|leaf| leaf.to_vec().into()).collect(),
+                    signer: signer.signer.into(),
+                    signature: signer.signature.into(),
+                    depth,
+                    merkle_mode: merkle_mode as u8,
+                    leaf_indices,
+                    hash_mode: hash_mode as u8,
+                    signature_scheme,
+                    bls_variant,
+                    bls_dst_hash: bls_dst_hash.into(),
+                    context_bound,
+                    domain: domain.into(),
+                    chain_id,
+                    epoch,
+                    signer_params: signer.params.into(),
+                }),
+                Statement::Sparse { key, value, siblings } => JournalPayload::Sparse(SparsePublicInputs {
+                    merkle_root: computed_root.to_vec().into(),
+                    key: key.to_vec().into(),
+                    absent: value.is_none(),
+                    value: value.map(|value| value.to_vec()).unwrap_or_default().into(),
+                    signer: signer.signer.into(),
+                    signature: signer.signature.into(),
+                    depth: siblings.len() as u32,
+                    hash_mode: hash_mode as u8,
+                    signature_scheme,
+                    bls_variant,
+                    bls_dst_hash: bls_dst_hash.into(),
+                    context_bound,
+                    domain: domain.into(),
+                    chain_id,
+                    epoch,
+                    signer_params: signer.params.into(),
+                }),
+                Statement::RecordStream { start_running_hash, record_hashes, position } => JournalPayload::RecordStream(RecordStreamPublicInputs {
+                    start_running_hash: start_running_hash.to_vec().into(),
+                    end_running_hash: computed_root.to_vec().into(),
+                    record_hash: record_hashes[position as usize].to_vec().into(),
+                    signer: signer.signer.into(),
+                    signature: signer.signature.into(),
+                    position,
+                    record_count: record_hashes.len() as u32,
+                    signature_scheme,
+                    bls_variant,
+                    bls_dst_hash: bls_dst_hash.into(),
+                    context_bound,
+                    domain: domain.into(),
+                    chain_id,
+                    epoch,
+                    signer_params: signer.params.into(),
+                    nullifier: nullifier.into(),
+                    nullifier_domain: nullifier_domain.into(),
+                }),
+            };
+            encode_journal(flags, &payload)
+        }
     };
     // Commit to the public values of the program.
-    env::commit_slice(&(public_inputs.abi_encode()));
+    env::commit_slice(&journal);
 
     let end = env::cycle_count();
     eprintln!("total cycle count: {}", end - start);
 
-}
\ No newline at end of file
+}
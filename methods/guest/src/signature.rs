@@ -0,0 +1,253 @@
+// Authentication of the computed root.
+
+use alloy_sol_types::SolValue;
+use rsa::{
+    pkcs1v15::{Signature as RsaSignature, VerifyingKey},
+    pkcs8::DecodePublicKey,
+    signature::Verifier,
+    traits::PublicKeyParts,
+    RsaPublicKey,
+};
+use sha2::{Digest, Sha384};
+
+use hedera_zk_core::address_book::{hash_address_book, quorum_reached, threshold_is_valid};
+use hedera_zk_core::bls::{self, BlsVariant, Ciphersuite};
+use hedera_zk_core::inputs::{AddressBookEntry, KeyValidation, NodeSignature, RootSignature};
+use hedera_zk_core::journal::{AggregateParams, QuorumParams, SignatureFileParams, SignatureScheme, ThresholdParams};
+use hedera_zk_core::merkle::merkle_tree_hash;
+use hedera_zk_core::message::{signed_message, SigningContext};
+use hedera_zk_core::sig_file::{metadata_hash, parse_signature_file};
+
+// Hedera node keys are RSA-3072.
+const NODE_KEY_SIZE: usize = 384;
+
+// What the journal records about the signer once the root is authenticated.
+pub struct SignerCommitment {
+    pub scheme: SignatureScheme,
+    pub signer: Vec<u8>, // the BLS public or group key, the SHA-384 hash of the RSA node key, the address book hash or the committee root
+    pub signature: Vec<u8>,
+    pub params: Vec<u8>, // ABI-encoded scheme parameters, empty unless noted on the scheme
+}
+
+fn verify_node_signature(node_pubkey: &[u8], message: &[u8], signature: &[u8]) {
+    let key = RsaPublicKey::from_public_key_der(node_pubkey).expect("Invalid node public key");
+    assert_eq!(key.size(), NODE_KEY_SIZE, "Node key is not RSA-3072");
+    let signature = RsaSignature::try_from(signature).expect("Invalid node signature");
+
+    VerifyingKey::<Sha384>::new(key)
+        .verify(message, &signature)
+        .expect("Invalid node signature");
+}
+
+// Hashes an address book of `variant` keys, which must be sorted by node id.
+pub fn address_book_hash(variant: BlsVariant, address_book: &[AddressBookEntry]) -> [u8; 48] {
+    assert!(address_book.windows(2).all(|pair| pair[0].node_id < pair[1].node_id), "Address book is not sorted by node id");
+
+    hash_address_book(variant, address_book.iter().map(|entry| (entry.node_id, entry.pubkey.as_slice(), entry.stake)))
+        .expect("Invalid address book")
+}
+
+// Panics unless nodes holding more than the threshold fraction of the stake in `address_book`
+// signed `message`. The threshold must be at least a third and below one. Invalid signatures are
+// ignored rather than rejected, and logged unless `private` is set. Returns the address book hash
+// and the stake that signed.
+pub fn verify_quorum(
+    ciphersuite: &Ciphersuite,
+    message: &[u8],
+    address_book: &[AddressBookEntry],
+    signatures: &[NodeSignature],
+    threshold_numerator: u64,
+    threshold_denominator: u64,
+    private: bool,
+) -> ([u8; 48], QuorumParams) {
+    assert!(threshold_is_valid(threshold_numerator, threshold_denominator), "Invalid quorum threshold");
+    let address_book_hash = address_book_hash(ciphersuite.variant, address_book);
+    assert!(signatures.windows(2).all(|pair| pair[0].node_id < pair[1].node_id), "Node signatures are not sorted by node id");
+
+    let total_stake = address_book.iter().try_fold(0u64, |total, entry| total.checked_add(entry.stake)).expect("Total stake overflow");
+    // Every node signs the same message, so it is hashed to the curve only once, and when every
+    // signature is valid, as it usually is, they are all checked with one pairing.
+    let prepared_message = bls::prepare_message(ciphersuite.variant, message, &ciphersuite.dst);
+    let mut signers = Vec::with_capacity(signatures.len());
+    for node_signature in signatures {
+        let index = address_book
+            .binary_search_by_key(&node_signature.node_id, |entry| entry.node_id)
+            .expect("Signature from a node outside the address book");
+        let entry = &address_book[index];
+
+        match (
+            bls::public_key(ciphersuite.variant, &entry.pubkey),
+            bls::signature(ciphersuite.variant, &node_signature.signature),
+        ) {
+            (Some(pubkey), Some(signature)) => signers.push((entry, pubkey, signature)),
+            _ if private => {}
+            _ => println!("Ignoring malformed signature from node {}", entry.node_id),
+        }
+    }
+
+    let batch: Vec<_> = signers.iter().map(|&(_, pubkey, signature)| (pubkey, signature)).collect();
+    let all_valid = bls::verify_batch(&prepared_message, &batch);
+    let mut signed_stake = 0u64;
+    for (entry, pubkey, signature) in &signers {
+        if all_valid || bls::verify_prepared(pubkey, &prepared_message, signature) {
+            signed_stake += entry.stake;
+        } else if !private {
+            println!("Ignoring invalid signature from node {}", entry.node_id);
+        }
+    }
+
+    if !private {
+        println!("Signed stake: {} of {}", signed_stake, total_stake);
+    }
+    assert!(
+        quorum_reached(signed_stake, total_stake, threshold_numerator, threshold_denominator),
+        "Signed stake below quorum threshold"
+    );
+
+    (address_book_hash, QuorumParams { threshold_numerator, threshold_denominator, signed_stake, total_stake })
+}
+
+// Panics unless `root_signature` is a valid signature over `root`, bound to `context` if one is
+// given. BLS signatures are checked under `ciphersuite`. Signature files also need the start
+// running hash of the record file whose end running hash is `root`. Nothing about the signers is
+// logged when `private` is set.
+pub fn verify_root_signature(
+    root: &[u8; 48],
+    start_running_hash: Option<&[u8; 48]>,
+    context: Option<&SigningContext>,
+    root_signature: &RootSignature,
+    ciphersuite: &Ciphersuite,
+    private: bool,
+) -> SignerCommitment {
+    let message = signed_message(context, root);
+
+    match root_signature {
+        RootSignature::Bls { pubkey, signature } => {
+            let bls_pubkey = bls::public_key(ciphersuite.variant, pubkey).expect("Invalid public key");
+            let bls_signature = bls::signature(ciphersuite.variant, signature).expect("Invalid signature");
+
+            assert!(bls::verify(&bls_pubkey, &message, &bls_signature, &ciphersuite.dst), "Invalid verification");
+
+            SignerCommitment {
+                scheme: SignatureScheme::Bls,
+                signer: pubkey.clone(),
+                signature: signature.clone(),
+                params: Vec::new(),
+            }
+        }
+        RootSignature::SignatureFile { node_pubkey, signature_file, header } => {
+            assert!(context.is_none(), "Signature files sign the bare metadata hash");
+            let start_running_hash = start_running_hash.expect("Signature files only sign record streams");
+            let signature_file = parse_signature_file(signature_file).expect("Invalid signature file");
+            let metadata_hash = metadata_hash(signature_file.version, header, start_running_hash, root)
+                .expect("Unsupported record file version");
+            assert_eq!(signature_file.metadata_hash, metadata_hash, "Signature file does not sign the running hashes");
+
+            verify_node_signature(node_pubkey, &metadata_hash, &signature_file.metadata_signature);
+
+            SignerCommitment {
+                scheme: SignatureScheme::RsaSignatureFile,
+                signer: Sha384::digest(node_pubkey).to_vec(),
+                signature: signature_file.metadata_signature,
+                params: SignatureFileParams {
+                    file_version: signature_file.version,
+                    hapi_major: header.hapi_version[0],
+                    hapi_minor: header.hapi_version[1],
+                    hapi_patch: header.hapi_version[2],
+                    block_number: if signature_file.version == 6 { header.block_number } else { 0 },
+                }
+                .abi_encode(),
+            }
+        }
+        RootSignature::Quorum { address_book, signatures, threshold_numerator, threshold_denominator } => {
+            let (address_book_hash, params) =
+                verify_quorum(ciphersuite, &message, address_book, signatures, *threshold_numerator, *threshold_denominator, private);
+
+            SignerCommitment {
+                scheme: SignatureScheme::BlsQuorum,
+                signer: address_book_hash.to_vec(),
+                signature: Vec::new(),
+                params: params.abi_encode(),
+            }
+        }
+        RootSignature::Aggregate { committee, participation, signature, key_validation } => {
+            let participants = bls::participants(committee.len(), participation).expect("Invalid participation bitmap");
+            let pubkeys: Vec<_> = participants
+                .iter()
+                .map(|&member| bls::public_key(ciphersuite.variant, &committee[member]).expect("Invalid committee public key"))
+                .collect();
+            let aggregate_signature = bls::signature(ciphersuite.variant, signature).expect("Invalid signature");
+
+            if let KeyValidation::ProofsOfPossession { proofs } = key_validation {
+                assert_eq!(proofs.len(), committee.len(), "Expected one proof of possession slot per committee member");
+                for (&member, pubkey) in participants.iter().zip(&pubkeys) {
+                    let proof = proofs[member]
+                        .as_ref()
+                        .unwrap_or_else(|| panic!("Missing proof of possession for committee member {}", member));
+                    let proof = bls::signature(ciphersuite.variant, proof)
+                        .unwrap_or_else(|| panic!("Malformed proof of possession for committee member {}", member));
+                    assert!(bls::verify_possession(pubkey, &proof), "Invalid proof of possession for committee member {}", member);
+                }
+            }
+
+            if !private {
+                println!("Participants: {} of {}", participants.len(), committee.len());
+            }
+
+            let aggregate_pubkey = bls::aggregate_public_keys(ciphersuite.variant, &pubkeys).expect("Mixed BLS variants");
+            assert!(bls::verify(&aggregate_pubkey, &message, &aggregate_signature, &ciphersuite.dst), "Invalid aggregate signature");
+
+            SignerCommitment {
+                scheme: SignatureScheme::BlsAggregate,
+                signer: merkle_tree_hash(committee).to_vec(),
+                signature: signature.clone(),
+                params: AggregateParams {
+                    committee_size: committee.len() as u32,
+                    participation: participation.clone().into(),
+                    key_validation: match key_validation {
+                        KeyValidation::ProofsOfPossession { .. } => 0,
+                        KeyValidation::Registry => 1,
+                    },
+                }
+                .abi_encode(),
+            }
+        }
+        RootSignature::Threshold { group_pubkey, threshold, key_shares, shares } => {
+            assert!(*threshold > 0 && *threshold as usize <= key_shares.len(), "Invalid signing threshold");
+            assert_eq!(shares.len(), *threshold as usize, "Expected exactly threshold signature shares");
+            assert!(shares.windows(2).all(|pair| pair[0].index < pair[1].index), "Signature shares are not sorted by index");
+
+            let group_key = bls::public_key(ciphersuite.variant, group_pubkey).expect("Invalid group public key");
+            let prepared_message = bls::prepare_message(ciphersuite.variant, &message, &ciphersuite.dst);
+            let indices: Vec<u64> = shares.iter().map(|share| share.index).collect();
+            let batch: Vec<_> = shares
+                .iter()
+                .map(|share| {
+                    let key_share = usize::try_from(share.index)
+                        .ok()
+                        .and_then(|index| index.checked_sub(1))
+                        .and_then(|index| key_shares.get(index))
+                        .expect("Signature share from a signer outside the key set");
+                    (
+                        bls::public_key(ciphersuite.variant, key_share).expect("Invalid public key share"),
+                        bls::signature(ciphersuite.variant, &share.signature).expect("Invalid signature share"),
+                    )
+                })
+                .collect();
+            // Without this, the group signature alone would pass as a single share of any threshold.
+            assert!(bls::verify_batch(&prepared_message, &batch), "Invalid signature share");
+
+            let partials: Vec<_> = batch.iter().map(|&(_, partial)| partial).collect();
+            let combined = bls::combine_signature_shares(&indices, &partials).expect("Invalid signer indices");
+            assert!(bls::verify_prepared(&group_key, &prepared_message, &combined), "Invalid threshold signature");
+
+            SignerCommitment {
+                scheme: SignatureScheme::BlsThreshold,
+                signer: group_pubkey.clone(),
+                signature: combined.to_bytes(),
+                params: ThresholdParams { key_set_id: bls::key_set_id(group_pubkey, *threshold, key_shares).to_vec().into() }
+                    .abi_encode(),
+            }
+        }
+    }
+}
@@ -2,139 +2,189 @@ include!(concat!(env!("OUT_DIR"), "/methods.rs"));
 
 #[cfg(test)]
 mod tests {
-    use alloy_primitives::FixedBytes;
-    //use bls_signatures::{PublicKey, PrivateKey, Signature, Serialize};
-
-    //use risc0_zkvm::{default_executor, ExecutorEnv, default_prover};
-    use risc0_groth16::docker::stark_to_snark;
-    use risc0_zkvm::{
-        get_prover_server, recursion::identity_p254, CompactReceipt, ExecutorEnv, ExecutorImpl,
-        InnerReceipt, ProverOpts, Receipt, VerifierContext,
-    };
-    use std::str::FromStr;
-    
-    use sha2::{Sha384, Digest};
-
-    use serde::Serialize;
-
-    // Function to verify a Merkle proof
-    fn compute_merkle_root(leaf: FixedBytes<48>, merkle_path: [[u8; 48]; 256]) -> [u8; 48] {
-        let mut hash = Sha384::digest(leaf.as_slice());
-        
-        for sibling in merkle_path {
-            let sibling_slice = sibling.as_slice();
-            let mut combined = Vec::with_capacity(96);
-            if hash.as_slice() < sibling_slice {
-                combined.extend_from_slice(&hash);
-                combined.extend_from_slice(&sibling_slice);
-            } else {
-                combined.extend_from_slice(&sibling_slice);
-                combined.extend_from_slice(&hash);
-            }
-            hash = Sha384::digest(&combined);
+    use bls_signatures::{PrivateKey, Serialize};
+    use risc0_zkvm::{ExecutorEnv, ExecutorImpl};
+    use sha2::{Digest, Sha384};
+
+    use hedera_zk_core::address_book::hash_address_book;
+    use hedera_zk_core::bls::{BlsVariant, Ciphersuite, MIN_PK_NUL_DST};
+    use hedera_zk_core::inputs::{AddressBookEntry, NodeSignature, PrivateInputs, RootSignature, RotationInputs, Statement};
+    use hedera_zk_core::journal::{decode_journal, DecodedJournal, JournalMode, JournalPayload};
+    use hedera_zk_core::merkle::{compute_merkle_root, hash_leaf, merkle_tree_hash, HashMode, MerkleMode};
+    use hedera_zk_core::record_stream::compute_running_hash;
+    use hedera_zk_core::sig_file::RecordFileHeader;
+    use hedera_zk_core::sparse::compute_sparse_root;
+
+    // Executes a guest without proving and decodes the journal it commits.
+    fn execute(elf: &[u8], input: &[u8]) -> DecodedJournal {
+        let env = ExecutorEnv::builder().write_slice(input).build().unwrap();
+        let session = ExecutorImpl::from_elf(env, elf).unwrap().run().unwrap();
+        decode_journal(&session.journal.unwrap().bytes).unwrap()
+    }
+
+    // Main program inputs proving `statement` about `root`, signed by a single min-pk BLS key.
+    fn signed_inputs(root: [u8; 48], merkle_mode: MerkleMode, hash_mode: HashMode, statement: Statement) -> PrivateInputs {
+        let key = PrivateKey::new([1; 32]);
+        PrivateInputs {
+            merkle_root: root,
+            context: None,
+            root_signature: RootSignature::Bls { pubkey: key.public_key().as_bytes(), signature: key.sign(root).as_bytes() },
+            ciphersuite: Ciphersuite { variant: BlsVariant::MinPk, dst: MIN_PK_NUL_DST.to_vec() },
+            merkle_mode,
+            hash_mode,
+            journal_mode: JournalMode::Full,
+            nullifier: None,
+            statement,
         }
-        
-        let mut result = [0u8; 48];
-        result.copy_from_slice(hash.as_slice());
-        result
     }
 
+    #[test]
+    fn test_execute_multiproof() {
+        let leaves: Vec<[u8; 48]> = (0u8..4).map(|i| Sha384::digest([i]).into()).collect();
+        let root = merkle_tree_hash(&leaves);
+
+        // Leaves 1 and 2 need their outer siblings; their parents are siblings of each other.
+        let proof = vec![hash_leaf(HashMode::Rfc6962, &leaves[0]), hash_leaf(HashMode::Rfc6962, &leaves[3])];
+        let statement = Statement::Multiproof { depth: 2, leaves: leaves[1..3].to_vec(), leaf_indices: vec![1, 2], proof };
+        let journal = execute(super::MAIN_ELF, &signed_inputs(root, MerkleMode::Indexed, HashMode::Rfc6962, statement).encode());
 
-    #[derive(Debug, Serialize)]
-    struct PrivateInputs {
-        pub merkle_root: FixedBytes<48>,
-        pub leaf: FixedBytes<48>,
-        pub bls_pubkey: FixedBytes<48>, // BLS public key size will always be 48 bytes
-        pub bls_signature: FixedBytes<96>, // BLS signature size will always be 96 bytes
-        pub serialized_path: FixedBytes<12288> // 48 * 256 length
+        let JournalPayload::Multiproof(public_inputs) = journal.payload else { panic!("Not a multiproof journal") };
+        assert_eq!(public_inputs.merkle_root.to_vec(), root.to_vec());
+        assert_eq!(public_inputs.leaf_indices, [1, 2]);
+        assert_eq!(journal.flags.depth, 2);
     }
 
-    impl PrivateInputs {
-        pub fn new(
-            merkle_root: FixedBytes<48>,
-            leaf: FixedBytes<48>,
-            bls_pubkey: FixedBytes<48>,
-            bls_signature: FixedBytes<96>,
-            serialized_path: FixedBytes<12288>,
-        ) -> Self {
-            Self {
-                merkle_root,
-                leaf,
-                bls_pubkey,
-                bls_signature,
-                serialized_path,
-            }
-        }
-    
+    #[test]
+    fn test_execute_sparse() {
+        let key = [0x80; 48];
+        let siblings = vec![None, None];
+        let root = compute_sparse_root(HashMode::Plain, &key, None, &siblings).unwrap();
+        let statement = Statement::Sparse { key, value: None, siblings };
+        let journal = execute(super::MAIN_ELF, &signed_inputs(root, MerkleMode::Indexed, HashMode::Plain, statement).encode());
+
+        let JournalPayload::Sparse(public_inputs) = journal.payload else { panic!("Not a sparse journal") };
+        assert_eq!(public_inputs.key.to_vec(), key.to_vec());
+        assert!(public_inputs.absent);
     }
 
     #[test]
-    fn test_verify() {
-
-        // Precomputed example inputs
-        let leaf_data = b"example leaf data";
-        let leaf_hash = Sha384::digest(leaf_data);
-        let leaf = FixedBytes::<48>::from_slice(leaf_hash.as_slice());
-
-        // 48 byte padded numbers 0 to 255 concatenated
-        let serialized_path = FixedBytes::<12288>::from_str("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009900000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000aa0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ab0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ac0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ad0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ae0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000af0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ba0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000bb0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000bc0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000bd0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000be0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000bf0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ca0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000cb0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000cc0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000cd0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ce0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000cf0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000da0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000db0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000dc0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000dd0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000de0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000df0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ea0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000eb0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ec0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ed0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ee0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ef0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f20000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f30000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f40000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f60000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f70000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f90000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000fa0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000fb0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000fc0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000fd0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000fe0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ff00000000000000000000000000000000").unwrap();
-
-        let merkle_path: [[u8; 48]; 256] = serialized_path.chunks_exact(48).map(|chunk| {
-            let mut arr = [0u8; 48];
-            arr.copy_from_slice(chunk);
-            arr
-        }).collect::<Vec<[u8; 48]>>().try_into().unwrap();
-
-        
-        let computed_root: [u8; 48] = compute_merkle_root(leaf.clone(), merkle_path);
-        println!("initial computed_root: {:?}", computed_root);
-
-        let merkle_root = FixedBytes::<48>::new(computed_root);
-        // let bls_privkey: PrivateKey = PrivateKey::generate(&mut rand::thread_rng());
-        // let bls_pubkey: PublicKey = bls_privkey.public_key();
-        // let bls_signature: Signature = bls_privkey.sign(&(merkle_root.as_slice()));
-
-        let bls_pubkey = FixedBytes::<48>::from_str("af991965245f23d0e8c498f95fb0293c3923f9ff68b24b93866570f49d9eb66afc19f156934f80edd52f42c3dcf41784").unwrap();
-        let bls_signature = FixedBytes::<96>::from_str("af08d283cbf25c4863294e49d08080b6bb9861e92d7ccc16434c4d355bf3c22f7858e7cb63fc3461d3250ce250822f9a1274952d7282c1f585a48b2572497c727beaf295ba817c5c112d5a4603c0fe864caeadab040c9ad5db47441ac8aef259").unwrap();
-        
-        let private_inputs = PrivateInputs::new(merkle_root, leaf, bls_pubkey, bls_signature, serialized_path);
-        //println!("{:?}", private_inputs);
-    
-        let env = ExecutorEnv::builder()
-            .write(&private_inputs).unwrap()
-            .build().unwrap();
-
-        // NOTE: Use the executor to run tests without proving.
-        // let session_info = default_executor().execute(env, super::MAIN_ELF).unwrap();
-        println!("Executing");
-        let mut exec = ExecutorImpl::from_elf(env, super::MAIN_ELF).unwrap();
-        let session = exec.run().unwrap();
-    
-        println!("prove");
-        let opts = ProverOpts::default();
-        let ctx = VerifierContext::default();
-        let prover = get_prover_server(&opts).unwrap();
-        // let receipt = prover.prove_session(&ctx, &session).unwrap().receipt;
-        // available fields are: `inner`, `journal`
-        let receipt: Receipt = prover.prove_session(&ctx, &session).unwrap();
-        let claim = receipt.get_claim().unwrap();
-        let composite_receipt = receipt.inner.composite().unwrap();
-        let succinct_receipt = prover.compress(composite_receipt).unwrap();
-        let journal = session.journal.unwrap().bytes;
-    
-        println!("identity_p254");
-        let ident_receipt = identity_p254(&succinct_receipt).unwrap();
-        let seal_bytes = ident_receipt.get_seal_bytes();
-    
-        println!("stark-to-snark");
-        let seal = stark_to_snark(&seal_bytes).unwrap().to_vec();
-    
-        println!("receipt");
-        let receipt = Receipt::new(
-            InnerReceipt::Compact(CompactReceipt { seal, claim }),
-            journal,
+    fn test_execute_record_stream() {
+        let start_running_hash: [u8; 48] = Sha384::digest(b"previous file").into();
+        let record_hashes: Vec<[u8; 48]> = (0u8..3).map(|i| Sha384::digest([i]).into()).collect();
+        let end_running_hash = compute_running_hash(&start_running_hash, &record_hashes);
+        let statement = Statement::RecordStream { start_running_hash, record_hashes: record_hashes.clone(), position: 1 };
+        let journal = execute(super::MAIN_ELF, &signed_inputs(end_running_hash, MerkleMode::Sorted, HashMode::Plain, statement).encode());
+
+        let JournalPayload::RecordStream(public_inputs) = journal.payload else { panic!("Not a record stream journal") };
+        assert_eq!(public_inputs.end_running_hash.to_vec(), end_running_hash.to_vec());
+        assert_eq!(public_inputs.record_hash.to_vec(), record_hashes[1].to_vec());
+        assert_eq!((public_inputs.position, public_inputs.record_count), (1, 3));
+    }
+
+    #[test]
+    fn test_execute_signature_file() {
+        // The synthetic record file described in core/fixtures/README.md, signed by a local RSA key.
+        let start_running_hash: [u8; 48] = Sha384::digest(b"synthetic start running hash").into();
+        let record_hashes: Vec<[u8; 48]> = (0u8..3).map(|i| Sha384::digest([i]).into()).collect();
+        let end_running_hash = compute_running_hash(&start_running_hash, &record_hashes);
+        let mut private_inputs = signed_inputs(
+            end_running_hash,
+            MerkleMode::Sorted,
+            HashMode::Plain,
+            Statement::RecordStream { start_running_hash, record_hashes, position: 2 },
         );
-    
-        // receipt.verify(super::MAIN_ELF).unwrap();
+        private_inputs.root_signature = RootSignature::SignatureFile {
+            node_pubkey: include_bytes!("../../core/fixtures/synthetic_node_key.der").to_vec(),
+            signature_file: include_bytes!("../../core/fixtures/synthetic_v6.rcd_sig").to_vec(),
+            header: RecordFileHeader { hapi_version: [0, 47, 0], block_number: 1000 },
+        };
+        let journal = execute(super::MAIN_ELF, &private_inputs.encode());
+
+        let JournalPayload::RecordStream(public_inputs) = journal.payload else { panic!("Not a record stream journal") };
+        assert_eq!(public_inputs.start_running_hash.to_vec(), start_running_hash.to_vec());
+        assert_eq!(public_inputs.end_running_hash.to_vec(), end_running_hash.to_vec());
+        assert_eq!(public_inputs.signature_scheme, 1);
+    }
+
+    #[test]
+    fn test_execute_rotation() {
+        let keys: Vec<PrivateKey> = (1..=3u8).map(|node| PrivateKey::new([node; 32])).collect();
+        let book = |keys: &[PrivateKey]| -> Vec<AddressBookEntry> {
+            keys.iter().zip(0u64..).map(|(key, node_id)| AddressBookEntry { node_id, pubkey: key.public_key().as_bytes(), stake: 10 }).collect()
+        };
+        let old_address_book = book(&keys[..2]);
+        let new_address_book = book(&keys);
+        let new_hash = hash_address_book(BlsVariant::MinPk, new_address_book.iter().map(|entry| (entry.node_id, entry.pubkey.as_slice(), entry.stake))).unwrap();
+
+        // One of two equally staked nodes is more than a third of the stake.
+        let rotation = RotationInputs {
+            old_address_book,
+            new_address_book,
+            signatures: vec![NodeSignature { node_id: 1, signature: keys[1].sign(new_hash).as_bytes() }],
+            threshold_numerator: 1,
+            threshold_denominator: 3,
+            ciphersuite: Ciphersuite { variant: BlsVariant::MinPk, dst: MIN_PK_NUL_DST.to_vec() },
+        };
+        let journal = execute(super::ROTATION_ELF, &rotation.encode());
+
+        let JournalPayload::Rotation(public_inputs) = journal.payload else { panic!("Not a rotation journal") };
+        assert_eq!(public_inputs.new_address_book_hash.to_vec(), new_hash.to_vec());
+        assert_eq!((public_inputs.signed_stake, public_inputs.total_stake), (10, 20));
+    }
+
+    // Executes the guest without proving and returns the cycle counts it prints to stderr, by label.
+    fn guest_cycle_counts(private_inputs: &PrivateInputs) -> Vec<(String, u64)> {
+        let mut stderr = Vec::new();
+        {
+            let env = ExecutorEnv::builder()
+                .write_slice(&private_inputs.encode())
+                .stderr(&mut stderr)
+                .build().unwrap();
+            ExecutorImpl::from_elf(env, super::MAIN_ELF).unwrap().run().unwrap();
+        }
+
+        String::from_utf8(stderr).unwrap()
+            .lines()
+            .filter_map(|line| line.split_once(" cycle count: "))
+            .map(|(label, count)| (label.to_string(), count.trim().parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    #[ignore = "executes the guest, run with --release -- --ignored --nocapture"]
+    fn bench_signature_cycles() {
+        let leaf: [u8; 48] = Sha384::digest(b"example leaf data").into();
+        let merkle_path = vec![[0u8; 48]];
+        let root = compute_merkle_root(MerkleMode::Indexed, HashMode::Rfc6962, &leaf, 0, &merkle_path);
+        let statement = Statement::Inclusion { leaf, leaf_index: 0, merkle_path };
+
+        // A single key signing the root, the baseline every quorum pays on top of.
+        let private_inputs = signed_inputs(root, MerkleMode::Indexed, HashMode::Rfc6962, statement.clone());
+        for (label, cycles) in guest_cycle_counts(&private_inputs) {
+            println!("single key, {}: {}", label, cycles);
+        }
+
+        for nodes in [1u8, 4, 16, 32] {
+            let keys: Vec<PrivateKey> = (1..=nodes).map(|node| PrivateKey::new([node; 32])).collect();
+            let address_book = keys
+                .iter()
+                .zip(0u64..)
+                .map(|(key, node_id)| AddressBookEntry { node_id, pubkey: key.public_key().as_bytes(), stake: 1 })
+                .collect();
+            let signatures = keys
+                .iter()
+                .zip(0u64..)
+                .map(|(key, node_id)| NodeSignature { node_id, signature: key.sign(root).as_bytes() })
+                .collect();
+
+            let private_inputs = PrivateInputs {
+                root_signature: RootSignature::Quorum { address_book, signatures, threshold_numerator: 2, threshold_denominator: 3 },
+                ..signed_inputs(root, MerkleMode::Indexed, HashMode::Rfc6962, statement.clone())
+            };
+
+            for (label, cycles) in guest_cycle_counts(&private_inputs) {
+                println!("{:>2} nodes, {}: {}", nodes, label, cycles);
+            }
+        }
     }
 }
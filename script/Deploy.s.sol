@@ -26,6 +26,10 @@ import {CommitmentVerification} from "../contracts/CommitmentVerification.sol";
 /// @notice Deployment script for the RISC Zero starter project.
 /// @dev Use the following environment variable to control the deployment:
 ///     * ETH_WALLET_PRIVATE_KEY private key of the wallet to be used for deployment.
+///     * ADDRESS_BOOK_HASH (optional) SHA-384 hash of the initially trusted address book.
+///     * BLS_DST_HASH (optional) SHA-384 hash of the DST its nodes sign under, by default the min-pk
+///       basic scheme tag BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_.
+///     * BLS_VARIANT (optional) its BLS key layout, 0 for min-pk (the default) or 1 for min-sig.
 ///
 /// See the Foundry documentation for more information about Solidity scripts.
 /// https://book.getfoundry.sh/tutorials/solidity-scripting
@@ -38,7 +42,14 @@ contract EvenNumberDeploy is Script {
         IRiscZeroVerifier verifier = new RiscZeroGroth16Verifier(ControlID.CONTROL_ID_0, ControlID.CONTROL_ID_1);
         console2.log("Deployed RiscZeroGroth16Verifier to", address(verifier));
 
-        CommitmentVerification commitmentVerification = new CommitmentVerification(verifier);
+        bytes memory addressBookHash = vm.envOr("ADDRESS_BOOK_HASH", new bytes(0));
+        bytes memory blsDstHash = vm.envOr(
+            "BLS_DST_HASH",
+            bytes(hex"c05ad1ba0b7461ac0239fae171a8f5d0fb3412aecd0d66f4755ee1e21311ff0b153bfb071747df0a1310e1129d6000ae")
+        );
+        uint8 blsVariant = uint8(vm.envOr("BLS_VARIANT", uint256(0)));
+        CommitmentVerification commitmentVerification =
+            new CommitmentVerification(verifier, addressBookHash, blsDstHash, blsVariant);
         console2.log("Deployed CommitmentVerification to", address(commitmentVerification));
 
         vm.stopBroadcast();
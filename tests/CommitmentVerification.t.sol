@@ -20,7 +20,7 @@ import {RiscZeroCheats} from "risc0/RiscZeroCheats.sol";
 import {console2} from "forge-std/console2.sol";
 import {Test} from "forge-std/Test.sol";
 import {IRiscZeroVerifier} from "risc0/IRiscZeroVerifier.sol";
-import {CommitmentVerification} from "../contracts/CommitmentVerification.sol";
+import {CommitmentVerification, CompactJournal, Journal, PublicInputs, QuorumParams} from "../contracts/CommitmentVerification.sol";
 import {Elf} from "./Elf.sol"; // auto-generated contract after running `cargo build`.
 
 import { Sha2Ext } from "./Sha2Ext.sol";
@@ -28,9 +28,12 @@ import { Sha2Ext } from "./Sha2Ext.sol";
 contract CommitmentVerificationTest is RiscZeroCheats, Test {
     CommitmentVerification public commitmentVerification;
 
+    // SHA-384 of the min-pk basic DST, BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_
+    bytes constant MIN_PK_NUL_DST_HASH = hex"c05ad1ba0b7461ac0239fae171a8f5d0fb3412aecd0d66f4755ee1e21311ff0b153bfb071747df0a1310e1129d6000ae";
+
     function setUp() public {
         IRiscZeroVerifier verifier = deployRiscZeroVerifier();
-        commitmentVerification = new CommitmentVerification(verifier);
+        commitmentVerification = new CommitmentVerification(verifier, new bytes(0), MIN_PK_NUL_DST_HASH, 0);
 
     }
 
@@ -52,37 +55,160 @@ contract CommitmentVerificationTest is RiscZeroCheats, Test {
         // generated using https://iancoleman.io/blsttc_ui/
         bytes memory blsSignature = hex"af08d283cbf25c4863294e49d08080b6bb9861e92d7ccc16434c4d355bf3c22f7858e7cb63fc3461d3250ce250822f9a1274952d7282c1f585a48b2572497c727beaf295ba817c5c112d5a4603c0fe864caeadab040c9ad5db47441ac8aef259";
 
-        bytes memory input = bytes.concat(merkleRoot, leaf, blsPubKey, blsSignature);
-
+        bytes memory blsDst = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+        // PrivateInputs in the guest's wire format (core/src/inputs.rs): big-endian integers,
+        // u32 length prefixes, u8 enum tags.
+        bytes memory input = bytes.concat(
+            merkleRoot,
+            abi.encodePacked(uint8(0)), // context: none, the bare root is signed
+            abi.encodePacked(uint8(0), uint32(blsPubKey.length), blsPubKey, uint32(blsSignature.length), blsSignature), // RootSignature::Bls
+            abi.encodePacked(uint8(0), uint32(blsDst.length), blsDst), // Ciphersuite: min-pk
+            abi.encodePacked(uint8(0), uint8(0), uint8(0)), // MerkleMode::Sorted, HashMode::Plain, JournalMode::Full
+            abi.encodePacked(uint8(0)), // nullifier: none
+            abi.encodePacked(uint8(0), leaf, uint64(0)), // Statement::Inclusion, leaf index 0
+            merklePathInput()
+        );
         bytes[] memory merklePath = new bytes[](256);
+
+        //console2.logBytes(input);
+
+
+        //(bytes memory journal, bytes32 post_state_digest, bytes memory seal) = prove(Elf.MAIN_PATH, abi.encodePacked(merkleRoot, leaf, blsPubKey, blsSignature, merklePath));
+        (bytes memory journal, bytes32 post_state_digest, bytes memory seal) = prove(Elf.MAIN_PATH, input);
+
+        Journal memory header = abi.decode(journal, (Journal));
+
+        require(header.version == 2, "journal version doesn't match");
+
+        require(header.statementType == 0, "statement type doesn't match");
+
+        // plain hashing, sorted pairs, min-pk, depth in bits 16-31
+        require(header.flags == uint32(merklePath.length) << 16, "journal flags don't match");
+
+        PublicInputs memory publicInputs = abi.decode(header.payload, (PublicInputs));
+
+        require(compareBytes(publicInputs.merkleRoot, merkleRoot), "merkle roots don't match");
+
+        require(compareBytes(publicInputs.leaf, leaf), "leaf doesn't match");
+
+        require(compareBytes(publicInputs.signer, blsPubKey), "pubKey doesn't match");
+
+        require(compareBytes(publicInputs.signature, blsSignature), "signature doesn't match");
+
+        require(publicInputs.depth == merklePath.length, "depth doesn't match");
+
+        // sorted-pair path with bare SHA-384, so no position is bound
+        require(publicInputs.merkleMode == 0 && publicInputs.leafIndex == 0, "merkle mode doesn't match");
+
+        require(publicInputs.hashMode == 0, "hash mode doesn't match");
+
+        // signed with BLS, so the signer is the raw public key
+        require(publicInputs.signatureScheme == 0, "signature scheme doesn't match");
+
+        // min-pk layout: 48-byte G1 public key, 96-byte G2 signature
+        require(publicInputs.blsVariant == 0, "BLS variant doesn't match");
+
+        // basic scheme tag, the one the test signature was made under
+        (bytes32 dstHashHigh, bytes16 dstHashLow) = Sha2Ext.sha384(blsDst);
+        require(compareBytes(publicInputs.blsDstHash, abi.encodePacked(dstHashHigh, dstHashLow)), "BLS DST doesn't match");
+
+        // the test signature is over the bare root
+        require(!publicInputs.contextBound && publicInputs.domain.length == 0, "unexpected signing context");
+
+        require(publicInputs.signerParams.length == 0, "unexpected signer params");
+
+        require(publicInputs.nullifier.length == 0 && publicInputs.nullifierDomain.length == 0, "unexpected nullifier");
+
+        commitmentVerification.verify(journal, post_state_digest, seal);
+    }
+
+    // The 256-element path from the test leaf to the test root, with its u32 length prefix.
+    function merklePathInput() internal pure returns (bytes memory input) {
+        input = abi.encodePacked(uint32(256));
         for (uint256 i = 0; i < 256; i++) {
             bytes memory element = new bytes(48);
             assembly {
                 mstore(add(add(element, 48), 0), i) // Store index value at the beginning of the bytes element
             }
-            merklePath[i] = element;
-            input = bytes.concat(input, merklePath[i]);
-
-            //console2.logBytes(merklePath[i]);
+            input = bytes.concat(input, element);
         }
+    }
 
-        //console2.logBytes(input);
-
+    // Inputs proving the test root signed by a one-node address book holding the test key, whose
+    // signature over the bare root is a quorum, and the hash of that book.
+    function quorumInput() internal pure returns (bytes memory input, bytes memory bookHash) {
+        bytes memory leaf = hex"0efa1c3008184a4b9c562a787d26b2084a4c4624095cfc448c4f3c6158a32d6af0489b873c794325cf128882666bc736";
+        bytes memory merkleRoot = hex"7656838873538ff82acc1c5925967f1193a97c684265e5f6d7c1ddb632caa26843a91106172a880e14c0e8bd9585f1a0";
+        bytes memory blsPubKey = hex"af991965245f23d0e8c498f95fb0293c3923f9ff68b24b93866570f49d9eb66afc19f156934f80edd52f42c3dcf41784";
+        bytes memory blsSignature = hex"af08d283cbf25c4863294e49d08080b6bb9861e92d7ccc16434c4d355bf3c22f7858e7cb63fc3461d3250ce250822f9a1274952d7282c1f585a48b2572497c727beaf295ba817c5c112d5a4603c0fe864caeadab040c9ad5db47441ac8aef259";
+        bytes memory blsDst = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+        // min-pk variant, then node 3 with its length-prefixed key and a stake of 1
+        (bytes32 bookHashHigh, bytes16 bookHashLow) = Sha2Ext.sha384(abi.encodePacked(uint8(0), uint64(3), uint32(48), blsPubKey, uint64(1)));
+        bookHash = abi.encodePacked(bookHashHigh, bookHashLow);
+        input = bytes.concat(
+            merkleRoot,
+            abi.encodePacked(uint8(0)), // context: none
+            abi.encodePacked(uint8(2), uint32(1), uint64(3), uint32(blsPubKey.length), blsPubKey, uint64(1)), // RootSignature::Quorum, address book
+            abi.encodePacked(uint32(1), uint64(3), uint32(blsSignature.length), blsSignature, uint64(1), uint64(3)), // signatures, 1/3 threshold
+            abi.encodePacked(uint8(0), uint32(blsDst.length), blsDst), // Ciphersuite: min-pk
+            abi.encodePacked(uint8(0), uint8(0), uint8(0), uint8(0)), // sorted, plain, full journal, no nullifier
+            abi.encodePacked(uint8(0), leaf, uint64(0)), // Statement::Inclusion, leaf index 0
+            merklePathInput()
+        );
+    }
 
-        //(bytes memory journal, bytes32 post_state_digest, bytes memory seal) = prove(Elf.MAIN_PATH, abi.encodePacked(merkleRoot, leaf, blsPubKey, blsSignature, merklePath));
+    function test_quorum_needs_current_address_book() public {
+        (bytes memory input, bytes memory bookHash) = quorumInput();
         (bytes memory journal, bytes32 post_state_digest, bytes memory seal) = prove(Elf.MAIN_PATH, input);
 
-        (bytes memory computedMerkleRoot, bytes memory computedLeaf, bytes memory computedPubkey, bytes memory computedSignature) = abi.decode(journal, (bytes, bytes, bytes, bytes));
+        Journal memory header = abi.decode(journal, (Journal));
+        require(uint8(header.flags >> 8) == 2, "signature scheme doesn't match");
+        require(compareBytes(abi.decode(header.payload, (PublicInputs)).signer, bookHash), "signer is not the address book hash");
 
-        require(compareBytes(computedMerkleRoot, merkleRoot), "merkle roots don't match");
+        CommitmentVerification trusting = new CommitmentVerification(deployRiscZeroVerifier(), bookHash, MIN_PK_NUL_DST_HASH, 0);
+        trusting.verify(journal, post_state_digest, seal);
 
-        require(compareBytes(computedLeaf, leaf), "leaf doesn't match");
+        vm.expectRevert("root not signed by the current address book");
+        commitmentVerification.verify(journal, post_state_digest, seal);
 
-        require(compareBytes(computedPubkey, blsPubKey), "pubKey doesn't match");
+        // The same book registered for another ciphersuite does not accept signatures under this one.
+        CommitmentVerification otherVariant = new CommitmentVerification(deployRiscZeroVerifier(), bookHash, MIN_PK_NUL_DST_HASH, 1);
+        vm.expectRevert("not signed under the trusted ciphersuite");
+        otherVariant.verify(journal, post_state_digest, seal);
 
-        require(compareBytes(computedSignature, blsSignature), "signature doesn't match");
+        // The journal records the threshold the quorum was checked against.
+        QuorumParams memory quorum = abi.decode(abi.decode(header.payload, (PublicInputs)).signerParams, (QuorumParams));
+        require(quorum.thresholdNumerator == 1 && quorum.thresholdDenominator == 3, "threshold doesn't match");
+        require(quorum.signedStake == 1 && quorum.totalStake == 1, "stake doesn't match");
+    }
 
-        commitmentVerification.verify(journal, post_state_digest, seal);
+    function test_compact_journal_layout() public {
+        bytes memory root = new bytes(48);
+        root[0] = 0x01;
+        bytes memory leaf = new bytes(48);
+        leaf[47] = 0x02;
+        bytes memory signer = hex"af991965245f23d0e8c498f95fb0293c3923f9ff68b24b93866570f49d9eb66afc19f156934f80edd52f42c3dcf41784";
+        bytes memory dstHash = MIN_PK_NUL_DST_HASH;
+        // No signing context: contextBound false, empty domain, zero chain id and epoch.
+        bytes32 signerHash = sha256(abi.encodePacked(uint32(signer.length), signer, dstHash, false, uint32(0), uint64(0), uint64(0)));
+
+        // version 2, inclusion, compact flag with depth 256, leaf index 7
+        bytes memory journal = abi.encodePacked(uint16(2), uint8(0), uint32(0x01000008), root, leaf, uint64(7), signerHash);
+        CompactJournal memory compact = commitmentVerification.decodeCompactJournal(journal);
+
+        require(compact.version == 2 && compact.statementType == 0, "header doesn't match");
+        require(compact.flags == 0x01000008, "flags don't match");
+        require(compareBytes(compact.merkleRoot, root) && compareBytes(compact.leaf, leaf), "hashes don't match");
+        require(compact.leafIndex == 7 && compact.signerHash == signerHash, "leaf index or signer doesn't match");
+
+        vm.expectRevert("invalid compact journal");
+        commitmentVerification.decodeCompactJournal(bytes.concat(journal, hex"00"));
+
+        bytes memory oldVersion = abi.encodePacked(uint16(1), uint8(0), uint32(0x01000008), root, leaf, uint64(7), signerHash);
+        vm.expectRevert("unsupported journal version");
+        commitmentVerification.decodeCompactJournal(oldVersion);
     }
 }
 